@@ -1,15 +1,121 @@
 // Author: Enkae (enkae.dev@pm.me)
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+#[cfg(windows)]
 use windows::{
     Win32::{
-        System::Com::{CoCreateInstance, CLSCTX_INPROC_SERVER},
+        System::Com::{CoCreateInstance, CoInitializeEx, CLSCTX_INPROC_SERVER, COINIT_MULTITHREADED},
         UI::Accessibility::{
             IUIAutomation, IUIAutomationElement, TreeScope_Children, CUIAutomation,
         },
     },
 };
 
+/// Platform abstraction for reading the accessibility tree. Each thread that
+/// needs one constructs its own: on Windows, UI Automation objects are
+/// apartment-threaded and can't be shared across threads.
+pub trait Capturer {
+    /// Captures just the currently focused element (no children walked).
+    fn focused_element(&self) -> Result<UIElement>;
+    /// Walks the whole tree from the desktop root, down to `max_depth`.
+    fn walk_tree(&self, max_depth: u32) -> Result<UIElement>;
+}
+
+/// The `Capturer` backed by Windows UI Automation.
+#[cfg(windows)]
+pub struct WindowsCapturer {
+    automation: IUIAutomation,
+}
+
+#[cfg(windows)]
+impl WindowsCapturer {
+    /// Initializes COM for the calling thread and creates a UI Automation
+    /// instance. Call once per thread that will use the resulting
+    /// `Capturer`.
+    pub fn new() -> Result<Self> {
+        unsafe { CoInitializeEx(None, COINIT_MULTITHREADED) }.ok()?;
+        let automation: IUIAutomation =
+            unsafe { CoCreateInstance(&CUIAutomation, None, CLSCTX_INPROC_SERVER)? };
+        Ok(Self { automation })
+    }
+}
+
+#[cfg(windows)]
+impl Capturer for WindowsCapturer {
+    fn focused_element(&self) -> Result<UIElement> {
+        let element = unsafe { self.automation.GetFocusedElement()? };
+
+        let name = unsafe {
+            element
+                .CurrentName()
+                .map(|s| s.to_string())
+                .unwrap_or_else(|_| String::from("Unknown"))
+        };
+
+        let control_type = unsafe {
+            element
+                .CurrentLocalizedControlType()
+                .map(|s| s.to_string())
+                .unwrap_or_else(|_| String::from("Unknown"))
+        };
+
+        let bounding_rectangle = unsafe {
+            element
+                .CurrentBoundingRectangle()
+                .map(|rect| {
+                    format!(
+                        "left={},top={},right={},bottom={}",
+                        rect.left, rect.top, rect.right, rect.bottom
+                    )
+                })
+                .unwrap_or_else(|_| String::from("Unknown"))
+        };
+
+        Ok(UIElement {
+            name,
+            control_type,
+            bounding_rectangle,
+            children: Vec::new(),
+        })
+    }
+
+    fn walk_tree(&self, max_depth: u32) -> Result<UIElement> {
+        let root_element = unsafe { self.automation.GetRootElement()? };
+        walk_tree(&root_element, 0, max_depth)
+    }
+}
+
+/// The `Capturer` used where no accessibility backend exists yet. Both
+/// methods error rather than panic, so callers (capture mode, the hybrid
+/// daemon's SCAN command) degrade to a logged error instead of crashing.
+#[cfg(not(windows))]
+pub struct UnsupportedCapturer;
+
+#[cfg(not(windows))]
+impl UnsupportedCapturer {
+    pub fn new() -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+#[cfg(not(windows))]
+impl Capturer for UnsupportedCapturer {
+    fn focused_element(&self) -> Result<UIElement> {
+        anyhow::bail!("Accessibility capture is not implemented on this platform yet")
+    }
+
+    fn walk_tree(&self, _max_depth: u32) -> Result<UIElement> {
+        anyhow::bail!("Accessibility capture is not implemented on this platform yet")
+    }
+}
+
+/// The `Capturer` implementation to use on the current platform.
+#[cfg(windows)]
+pub type PlatformCapturer = WindowsCapturer;
+#[cfg(not(windows))]
+pub type PlatformCapturer = UnsupportedCapturer;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UIElement {
     pub name: String,
@@ -29,6 +135,7 @@ impl UIElement {
     }
 }
 
+#[cfg(windows)]
 pub fn walk_tree(
     element: &IUIAutomationElement,
     depth: u32,
@@ -59,6 +166,7 @@ pub fn walk_tree(
     Ok(ui_element)
 }
 
+#[cfg(windows)]
 fn get_current_name(element: &IUIAutomationElement) -> Result<String> {
     unsafe {
         let name_bstr = element.CurrentName()?;
@@ -66,6 +174,7 @@ fn get_current_name(element: &IUIAutomationElement) -> Result<String> {
     }
 }
 
+#[cfg(windows)]
 fn get_current_control_type(element: &IUIAutomationElement) -> Result<String> {
     unsafe {
         let control_type_bstr = element.CurrentLocalizedControlType()?;
@@ -73,6 +182,7 @@ fn get_current_control_type(element: &IUIAutomationElement) -> Result<String> {
     }
 }
 
+#[cfg(windows)]
 fn get_current_bounding_rectangle(element: &IUIAutomationElement) -> Result<String> {
     unsafe {
         let rect = element.CurrentBoundingRectangle()?;
@@ -83,6 +193,156 @@ fn get_current_bounding_rectangle(element: &IUIAutomationElement) -> Result<Stri
     }
 }
 
+/// A `bounding_rectangle` string (`"left=..,top=..,right=..,bottom=.."`)
+/// parsed into numeric bounds, so element-targeted actions can compute a
+/// click point instead of relying on hardcoded coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoundingRect {
+    pub left: i32,
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+}
+
+impl BoundingRect {
+    /// Parses the `left=..,top=..,right=..,bottom=..` format produced by
+    /// `get_current_bounding_rectangle`.
+    pub fn parse(s: &str) -> Result<Self> {
+        let mut left = None;
+        let mut top = None;
+        let mut right = None;
+        let mut bottom = None;
+
+        for field in s.split(',') {
+            let (key, value) = field
+                .split_once('=')
+                .ok_or_else(|| anyhow::anyhow!("Malformed bounding rectangle field: {}", field))?;
+            let value: i32 = value
+                .trim()
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Non-numeric bounding rectangle value: {}", field))?;
+
+            match key.trim() {
+                "left" => left = Some(value),
+                "top" => top = Some(value),
+                "right" => right = Some(value),
+                "bottom" => bottom = Some(value),
+                other => anyhow::bail!("Unknown bounding rectangle field: {}", other),
+            }
+        }
+
+        Ok(Self {
+            left: left.ok_or_else(|| anyhow::anyhow!("Missing 'left' in bounding rectangle: {}", s))?,
+            top: top.ok_or_else(|| anyhow::anyhow!("Missing 'top' in bounding rectangle: {}", s))?,
+            right: right.ok_or_else(|| anyhow::anyhow!("Missing 'right' in bounding rectangle: {}", s))?,
+            bottom: bottom
+                .ok_or_else(|| anyhow::anyhow!("Missing 'bottom' in bounding rectangle: {}", s))?,
+        })
+    }
+
+    /// The center point of the rectangle, suitable for `execute_click`.
+    pub fn center(&self) -> (i32, i32) {
+        ((self.left + self.right) / 2, (self.top + self.bottom) / 2)
+    }
+}
+
+/// Error returned when an element-targeted action can't be resolved to a
+/// single node, so the Permission Kernel can ask the operator to
+/// disambiguate instead of clicking the wrong thing.
+#[derive(Debug)]
+pub enum ElementMatchError {
+    /// No element in the tree matched the given `name`/`control_type`.
+    NoMatch { name: Option<String>, control_type: Option<String> },
+    /// More than one element matched; lists each candidate's name so the
+    /// caller can narrow the query.
+    Ambiguous { candidates: Vec<String> },
+}
+
+impl fmt::Display for ElementMatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ElementMatchError::NoMatch { name, control_type } => write!(
+                f,
+                "No element matched name={:?} control_type={:?}",
+                name, control_type
+            ),
+            ElementMatchError::Ambiguous { candidates } => write!(
+                f,
+                "Ambiguous target: {} elements matched ({})",
+                candidates.len(),
+                candidates.join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ElementMatchError {}
+
+/// Recursively searches `tree` for elements whose name and/or control type
+/// match (case-insensitive substring match on `name`, exact
+/// case-insensitive match on `control_type`). Either filter may be omitted.
+pub fn find_elements<'a>(
+    tree: &'a UIElement,
+    name: Option<&str>,
+    control_type: Option<&str>,
+) -> Vec<&'a UIElement> {
+    let mut matches = Vec::new();
+    find_elements_into(tree, name, control_type, &mut matches);
+    matches
+}
+
+fn find_elements_into<'a>(
+    element: &'a UIElement,
+    name: Option<&str>,
+    control_type: Option<&str>,
+    out: &mut Vec<&'a UIElement>,
+) {
+    let name_matches = name
+        .map(|n| element.name.to_lowercase().contains(&n.to_lowercase()))
+        .unwrap_or(true);
+    let control_type_matches = control_type
+        .map(|c| element.control_type.eq_ignore_ascii_case(c))
+        .unwrap_or(true);
+
+    if name_matches && control_type_matches {
+        out.push(element);
+    }
+
+    for child in &element.children {
+        find_elements_into(child, name, control_type, out);
+    }
+}
+
+/// Resolves `name`/`control_type` to the click-center of a single matching
+/// element, erroring if zero or more than one element match.
+pub fn resolve_element_center(
+    tree: &UIElement,
+    name: Option<&str>,
+    control_type: Option<&str>,
+) -> std::result::Result<(i32, i32), ElementMatchError> {
+    let matches = find_elements(tree, name, control_type);
+
+    match matches.as_slice() {
+        [] => Err(ElementMatchError::NoMatch {
+            name: name.map(str::to_string),
+            control_type: control_type.map(str::to_string),
+        }),
+        [only] => {
+            let rect = BoundingRect::parse(&only.bounding_rectangle).map_err(|_| {
+                ElementMatchError::NoMatch {
+                    name: name.map(str::to_string),
+                    control_type: control_type.map(str::to_string),
+                }
+            })?;
+            Ok(rect.center())
+        }
+        many => Err(ElementMatchError::Ambiguous {
+            candidates: many.iter().map(|e| e.name.clone()).collect(),
+        }),
+    }
+}
+
+#[cfg(windows)]
 fn get_child_elements(element: &IUIAutomationElement) -> Result<Vec<IUIAutomationElement>> {
     unsafe {
         let automation: IUIAutomation = CoCreateInstance(
@@ -103,3 +363,83 @@ fn get_child_elements(element: &IUIAutomationElement) -> Result<Vec<IUIAutomatio
         Ok(children)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounding_rect_parse_happy_path_and_center() {
+        let rect = BoundingRect::parse("left=0,top=10,right=100,bottom=50").unwrap();
+        assert_eq!(rect, BoundingRect { left: 0, top: 10, right: 100, bottom: 50 });
+        assert_eq!(rect.center(), (50, 30));
+    }
+
+    #[test]
+    fn bounding_rect_parse_rejects_missing_field() {
+        let err = BoundingRect::parse("left=0,top=10,right=100").unwrap_err();
+        assert!(err.to_string().contains("Missing 'bottom'"));
+    }
+
+    #[test]
+    fn bounding_rect_parse_rejects_non_numeric_value() {
+        let err = BoundingRect::parse("left=zero,top=10,right=100,bottom=50").unwrap_err();
+        assert!(err.to_string().contains("Non-numeric"));
+    }
+
+    #[test]
+    fn bounding_rect_parse_rejects_unknown_field() {
+        let err = BoundingRect::parse("left=0,top=10,right=100,bottom=50,depth=5").unwrap_err();
+        assert!(err.to_string().contains("Unknown bounding rectangle field: depth"));
+    }
+
+    fn leaf(name: &str, control_type: &str) -> UIElement {
+        UIElement::new(
+            name.to_string(),
+            control_type.to_string(),
+            "left=0,top=0,right=10,bottom=10".to_string(),
+        )
+    }
+
+    #[test]
+    fn resolve_element_center_errors_on_no_match() {
+        let tree = leaf("Button", "Button");
+
+        let err = resolve_element_center(&tree, Some("Missing"), None).unwrap_err();
+        assert!(matches!(err, ElementMatchError::NoMatch { .. }));
+    }
+
+    #[test]
+    fn resolve_element_center_resolves_a_single_match() {
+        let mut tree = UIElement::new(
+            "Root".to_string(),
+            "Window".to_string(),
+            "left=0,top=0,right=0,bottom=0".to_string(),
+        );
+        tree.children.push(UIElement::new(
+            "Submit".to_string(),
+            "Button".to_string(),
+            "left=0,top=0,right=20,bottom=10".to_string(),
+        ));
+
+        let center = resolve_element_center(&tree, Some("submit"), Some("Button")).unwrap();
+        assert_eq!(center, (10, 5));
+    }
+
+    #[test]
+    fn resolve_element_center_errors_on_ambiguous_match() {
+        let mut tree = UIElement::new(
+            "Root".to_string(),
+            "Window".to_string(),
+            "left=0,top=0,right=0,bottom=0".to_string(),
+        );
+        tree.children.push(leaf("Submit", "Button"));
+        tree.children.push(leaf("Submit Order", "Button"));
+
+        let err = resolve_element_center(&tree, Some("submit"), Some("Button")).unwrap_err();
+        match err {
+            ElementMatchError::Ambiguous { candidates } => assert_eq!(candidates.len(), 2),
+            other => panic!("expected Ambiguous, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,191 @@
+// Author: Enkae (enkae.dev@pm.me)
+//! Batches outgoing artifacts instead of one blocking POST per file.
+//! `ArtifactQueue::send` enqueues and returns immediately; a background
+//! flusher thread drains the queue into `POST {api_url}/batch` bodies,
+//! retrying failed batches with exponential backoff and spilling to disk
+//! when every retry is exhausted, so artifacts survive a backend outage.
+
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::time::{Duration, Instant};
+
+/// Max artifacts per `POST /artifacts/batch` body.
+const DEFAULT_MAX_BATCH_SIZE: usize = 50;
+/// Longest a partial batch waits for more items before flushing anyway.
+const DEFAULT_MAX_LINGER: Duration = Duration::from_millis(500);
+/// Retry backoff for a failed batch: base delay, doubled per attempt, capped.
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(250);
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(8);
+/// Retries attempted before a batch is spilled to disk.
+const MAX_RETRIES: u32 = 4;
+
+/// A non-blocking handle to the artifact submission subsystem. Cloning
+/// shares the same background flusher (and its queue) between callers.
+#[derive(Clone)]
+pub struct ArtifactQueue {
+    tx: Sender<serde_json::Value>,
+}
+
+impl ArtifactQueue {
+    /// Spawns the background flusher with the repo's default batch size
+    /// and linger time, and returns a handle to it.
+    pub fn start(api_url: String) -> Self {
+        Self::start_with(api_url, DEFAULT_MAX_BATCH_SIZE, DEFAULT_MAX_LINGER)
+    }
+
+    /// Spawns the background flusher with a custom batch size and linger
+    /// time. The flusher POSTs batches to `{api_url}/batch`.
+    pub fn start_with(api_url: String, max_batch_size: usize, max_linger: Duration) -> Self {
+        let (tx, rx) = channel();
+        std::thread::spawn(move || run_flusher(api_url, max_batch_size, max_linger, rx));
+        Self { tx }
+    }
+
+    /// Enqueues `artifact` for the background flusher. Never blocks on the
+    /// network; only errors if the flusher thread has died.
+    pub fn send(&self, artifact: serde_json::Value) -> Result<()> {
+        self.tx
+            .send(artifact)
+            .context("Artifact queue's flusher thread is gone")
+    }
+}
+
+/// Where artifacts go when every batch retry fails, so a backend outage
+/// doesn't lose anything already handed to the queue.
+fn spill_path() -> PathBuf {
+    std::env::temp_dir().join("ghost-artifact-spill.jsonl")
+}
+
+/// Appends each artifact in `batch` as one JSON line to the spill file.
+fn spill_to_disk(batch: &[serde_json::Value]) {
+    use std::io::Write;
+
+    let path = spill_path();
+    match std::fs::OpenOptions::new().create(true).append(true).open(&path) {
+        Ok(mut file) => {
+            for artifact in batch {
+                if let Ok(line) = serde_json::to_string(artifact) {
+                    let _ = writeln!(file, "{}", line);
+                }
+            }
+        }
+        Err(e) => eprintln!(
+            "[ARTIFACT_QUEUE] Failed to open spill file {}: {}",
+            path.display(),
+            e
+        ),
+    }
+}
+
+/// Reads and deletes any artifacts spilled during a previous outage, so
+/// they get retried ahead of anything freshly enqueued.
+fn drain_spilled_artifacts() -> Vec<serde_json::Value> {
+    let path = spill_path();
+    let Ok(contents) = std::fs::read_to_string(&path) else {
+        return Vec::new();
+    };
+
+    let artifacts = contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    let _ = std::fs::remove_file(&path);
+    artifacts
+}
+
+/// POSTs `batch` to `{api_url}/batch`, retrying with exponential backoff.
+/// Spills the whole batch to disk if every attempt fails.
+fn send_batch_with_retry(client: &reqwest::blocking::Client, api_url: &str, batch: &[serde_json::Value]) {
+    let batch_url = format!("{}/batch", api_url);
+    let mut delay = RETRY_BASE_DELAY;
+
+    for attempt in 0..=MAX_RETRIES {
+        let result = client
+            .post(&batch_url)
+            .json(&serde_json::json!({ "artifacts": batch }))
+            .send()
+            .and_then(|resp| resp.error_for_status());
+
+        match result {
+            Ok(_) => return,
+            Err(e) => {
+                eprintln!(
+                    "[ARTIFACT_QUEUE] Batch POST failed (attempt {}/{}): {}",
+                    attempt + 1,
+                    MAX_RETRIES + 1,
+                    e
+                );
+                if attempt < MAX_RETRIES {
+                    std::thread::sleep(delay);
+                    delay = (delay * 2).min(RETRY_MAX_DELAY);
+                }
+            }
+        }
+    }
+
+    eprintln!(
+        "[ARTIFACT_QUEUE] Giving up on a batch of {} artifact(s) after {} attempts; spilling to disk",
+        batch.len(),
+        MAX_RETRIES + 1
+    );
+    spill_to_disk(batch);
+}
+
+/// The flusher's main loop: collects items until `max_batch_size` is
+/// reached or `max_linger` passes since the batch's first item, then sends.
+fn run_flusher(
+    api_url: String,
+    max_batch_size: usize,
+    max_linger: Duration,
+    rx: Receiver<serde_json::Value>,
+) {
+    let client = reqwest::blocking::Client::new();
+
+    let spilled = drain_spilled_artifacts();
+    if !spilled.is_empty() {
+        println!(
+            "[ARTIFACT_QUEUE] Replaying {} artifact(s) spilled during a prior outage",
+            spilled.len()
+        );
+        send_batch_with_retry(&client, &api_url, &spilled);
+    }
+
+    let mut batch: Vec<serde_json::Value> = Vec::new();
+    let mut batch_started_at: Option<Instant> = None;
+
+    loop {
+        let wait = match batch_started_at {
+            Some(started) => max_linger.saturating_sub(started.elapsed()),
+            None => max_linger,
+        };
+
+        match rx.recv_timeout(wait) {
+            Ok(artifact) => {
+                if batch.is_empty() {
+                    batch_started_at = Some(Instant::now());
+                }
+                batch.push(artifact);
+
+                if batch.len() >= max_batch_size {
+                    send_batch_with_retry(&client, &api_url, &batch);
+                    batch.clear();
+                    batch_started_at = None;
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                if !batch.is_empty() {
+                    send_batch_with_retry(&client, &api_url, &batch);
+                    batch.clear();
+                    batch_started_at = None;
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                if !batch.is_empty() {
+                    send_batch_with_retry(&client, &api_url, &batch);
+                }
+                break;
+            }
+        }
+    }
+}
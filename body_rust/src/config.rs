@@ -0,0 +1,200 @@
+// Author: Enkae (enkae.dev@pm.me)
+//! Declarative macro configuration.
+//!
+//! Operators can define named, reusable action flows in a RON or JSON5 file
+//! instead of relying on the hardcoded intent-string dispatch in
+//! `effector::Effector::execute_action`. A macro is an ordered list of
+//! `ActionStep`s, each one of the existing payload shapes plus a per-step
+//! delay, e.g.:
+//!
+//! ```ron
+//! (
+//!     macros: {
+//!         "open_run_dialog": [
+//!             (action: PressKey("win+r"), delay_ms: 200),
+//!             (action: TypeText("notepad"), delay_ms: 100),
+//!             (action: PressKey("enter"), delay_ms: 0),
+//!         ],
+//!     },
+//! )
+//! ```
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// One step of a macro: an action plus how long to wait after it runs.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ActionStep {
+    pub action: ActionStepKind,
+    #[serde(default)]
+    pub delay_ms: u64,
+}
+
+/// The action performed by a single macro step. Mirrors the payload shapes
+/// `Effector` already understands.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub enum ActionStepKind {
+    TypeText(String),
+    Click { x: i32, y: i32 },
+    PressKey(String),
+    /// A chorded sequence, e.g. hold Ctrl, tap K then B, release Ctrl —
+    /// expressed as `Chord([Hold("ctrl"), Tap("k"), Tap("b"), Release("ctrl")])`.
+    Chord(Vec<ChordOp>),
+}
+
+/// One operation in a `Chord` step. `Hold` presses a key and keeps it down
+/// across later taps; `Tap` presses and releases immediately; `Release`
+/// lets a previously-held key go.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub enum ChordOp {
+    Hold(String),
+    Tap(String),
+    Release(String),
+}
+
+/// Top-level macro configuration: a named map of macros, each an ordered
+/// list of steps.
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub struct Config {
+    pub macros: HashMap<String, Vec<ActionStep>>,
+}
+
+impl Config {
+    /// Load and validate a config file. The format is chosen from the file
+    /// extension: `.ron` for RON, `.json5`/`.json` for JSON5.
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+
+        let config: Config = match path.extension().and_then(|e| e.to_str()) {
+            Some("ron") => ron::from_str(&raw)
+                .with_context(|| format!("Failed to parse RON config: {}", path.display()))?,
+            Some("json5") | Some("json") => json5::from_str(&raw)
+                .with_context(|| format!("Failed to parse JSON5 config: {}", path.display()))?,
+            other => bail!(
+                "Unsupported config extension {:?} for {}; expected .ron or .json5",
+                other,
+                path.display()
+            ),
+        };
+
+        config.validate().with_context(|| {
+            format!("Invalid macro config in {}", path.display())
+        })?;
+        Ok(config)
+    }
+
+    /// Validate every macro step, failing with the offending macro name and
+    /// step index so operators can fix the config without guessing.
+    fn validate(&self) -> Result<()> {
+        for (name, steps) in &self.macros {
+            if steps.is_empty() {
+                bail!("macro '{}' has no steps", name);
+            }
+            for (index, step) in steps.iter().enumerate() {
+                match &step.action {
+                    ActionStepKind::TypeText(text) if text.is_empty() => {
+                        bail!("macro '{}' step {}: empty TypeText", name, index);
+                    }
+                    ActionStepKind::PressKey(key_str) => {
+                        crate::keybind::parse_keybind_keys(key_str)
+                            .map_err(|e| anyhow::anyhow!(e))
+                            .with_context(|| {
+                                format!("macro '{}' step {}: invalid key", name, index)
+                            })?;
+                    }
+                    ActionStepKind::Chord(ops) => {
+                        for op in ops {
+                            let (ChordOp::Hold(k) | ChordOp::Tap(k) | ChordOp::Release(k)) = op;
+                            crate::keybind::Key::parse(k).map_err(|e| anyhow::anyhow!(e)).with_context(
+                                || format!("macro '{}' step {}: invalid chord key", name, index),
+                            )?;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Look up a macro by name.
+    pub fn get_macro(&self, name: &str) -> Option<&[ActionStep]> {
+        self.macros.get(name).map(Vec::as_slice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn single_step_config(action: ActionStepKind) -> Config {
+        let mut macros = HashMap::new();
+        macros.insert("m".to_string(), vec![ActionStep { action, delay_ms: 0 }]);
+        Config { macros }
+    }
+
+    #[test]
+    fn validate_rejects_empty_macro() {
+        let mut macros = HashMap::new();
+        macros.insert("empty".to_string(), Vec::new());
+        let config = Config { macros };
+
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("macro 'empty' has no steps"));
+    }
+
+    #[test]
+    fn validate_rejects_empty_type_text() {
+        let config = single_step_config(ActionStepKind::TypeText(String::new()));
+
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("empty TypeText"));
+    }
+
+    #[test]
+    fn validate_rejects_invalid_press_key() {
+        let config = single_step_config(ActionStepKind::PressKey("not-a-key".to_string()));
+
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("invalid key"));
+    }
+
+    #[test]
+    fn validate_rejects_invalid_chord_key() {
+        let config = single_step_config(ActionStepKind::Chord(vec![ChordOp::Hold(
+            "not-a-key".to_string(),
+        )]));
+
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("invalid chord key"));
+    }
+
+    #[test]
+    fn load_round_trips_a_ron_fixture() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("ghost_config_test_macros.ron");
+        std::fs::write(
+            &path,
+            r#"(
+    macros: {
+        "open_run_dialog": [
+            (action: PressKey("win+r"), delay_ms: 200),
+            (action: TypeText("notepad"), delay_ms: 100),
+        ],
+    },
+)"#,
+        )
+        .unwrap();
+
+        let config = Config::load(&path).unwrap();
+        let steps = config.get_macro("open_run_dialog").unwrap();
+        assert_eq!(steps.len(), 2);
+        assert!(matches!(steps[0].action, ActionStepKind::PressKey(_)));
+        assert_eq!(steps[0].delay_ms, 200);
+
+        std::fs::remove_file(&path).ok();
+    }
+}
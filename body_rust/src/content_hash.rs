@@ -0,0 +1,79 @@
+// Author: Enkae (enkae.dev@pm.me)
+//! Content-addressed hashing for dedup and unchanged-file detection. Hashing
+//! streams through a fixed-size buffer so a multi-gigabyte file never pulls
+//! more than a few megabytes into memory at once.
+
+use anyhow::{Context, Result};
+use std::io::Read;
+use std::path::Path;
+
+/// Read buffer size for `hash_file`. Large enough to amortize syscalls,
+/// small enough to keep memory bounded on huge files.
+const HASH_CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
+/// BLAKE3 digest of `path`'s contents, as a lowercase hex string.
+pub fn hash_file(path: &Path) -> Result<String> {
+    let mut file = std::fs::File::open(path)
+        .with_context(|| format!("Failed to open file for hashing: {}", path.display()))?;
+    let mut hasher = blake3::Hasher::new();
+    let mut buf = vec![0u8; HASH_CHUNK_SIZE];
+
+    loop {
+        let n = file
+            .read(&mut buf)
+            .with_context(|| format!("Failed to read file for hashing: {}", path.display()))?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_file_is_stable_for_identical_content() {
+        let dir = std::env::temp_dir();
+        let a = dir.join("ghost_hash_test_a.txt");
+        let b = dir.join("ghost_hash_test_b.txt");
+        std::fs::write(&a, b"identical content").unwrap();
+        std::fs::write(&b, b"identical content").unwrap();
+
+        assert_eq!(hash_file(&a).unwrap(), hash_file(&b).unwrap());
+
+        std::fs::remove_file(&a).ok();
+        std::fs::remove_file(&b).ok();
+    }
+
+    #[test]
+    fn hash_file_differs_for_different_content() {
+        let dir = std::env::temp_dir();
+        let a = dir.join("ghost_hash_test_c.txt");
+        let b = dir.join("ghost_hash_test_d.txt");
+        std::fs::write(&a, b"one").unwrap();
+        std::fs::write(&b, b"two").unwrap();
+
+        assert_ne!(hash_file(&a).unwrap(), hash_file(&b).unwrap());
+
+        std::fs::remove_file(&a).ok();
+        std::fs::remove_file(&b).ok();
+    }
+
+    #[test]
+    fn hash_file_handles_content_spanning_multiple_chunks() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("ghost_hash_test_large.bin");
+        let data = vec![7u8; HASH_CHUNK_SIZE + 1024];
+        std::fs::write(&path, &data).unwrap();
+
+        let streamed = hash_file(&path).unwrap();
+        let direct = blake3::hash(&data).to_hex().to_string();
+        assert_eq!(streamed, direct);
+
+        std::fs::remove_file(&path).ok();
+    }
+}
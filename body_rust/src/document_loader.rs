@@ -0,0 +1,190 @@
+// Author: Enkae (enkae.dev@pm.me)
+//! Pluggable document text extraction, so `DOCUMENT` artifacts carry real
+//! content for retrieval-augmented generation instead of just
+//! `path | name | TYPE`. Each loader knows which extensions it handles and
+//! how to turn a file into plain text; `load_document` picks the right one.
+
+use anyhow::{Context, Result};
+use std::io::Read;
+use std::path::Path;
+
+/// A source of plain text for one family of document extensions.
+pub trait DocumentLoader {
+    /// Whether this loader handles files with the given (lowercased, no
+    /// leading dot) extension.
+    fn supports(ext: &str) -> bool
+    where
+        Self: Sized;
+
+    /// Extracts the full plain-text content of `path`.
+    fn load(path: &Path) -> Result<String>
+    where
+        Self: Sized;
+}
+
+/// Raw read for formats that are already plain text.
+pub struct PlainTextLoader;
+
+impl DocumentLoader for PlainTextLoader {
+    fn supports(ext: &str) -> bool {
+        matches!(ext, "txt" | "md")
+    }
+
+    fn load(path: &Path) -> Result<String> {
+        std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read text file: {}", path.display()))
+    }
+}
+
+/// Extracts text from a PDF via `pdf-extract`.
+pub struct PdfLoader;
+
+impl DocumentLoader for PdfLoader {
+    fn supports(ext: &str) -> bool {
+        ext == "pdf"
+    }
+
+    fn load(path: &Path) -> Result<String> {
+        pdf_extract::extract_text(path)
+            .with_context(|| format!("Failed to extract PDF text: {}", path.display()))
+    }
+}
+
+/// Extracts text from a `.docx` by unzipping it and stripping tags out of
+/// `word/document.xml`.
+pub struct DocxLoader;
+
+impl DocumentLoader for DocxLoader {
+    fn supports(ext: &str) -> bool {
+        ext == "docx"
+    }
+
+    fn load(path: &Path) -> Result<String> {
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("Failed to open docx: {}", path.display()))?;
+        let mut archive = zip::ZipArchive::new(file)
+            .with_context(|| format!("Failed to read docx zip: {}", path.display()))?;
+
+        let mut xml = String::new();
+        archive
+            .by_name("word/document.xml")
+            .with_context(|| format!("docx missing word/document.xml: {}", path.display()))?
+            .read_to_string(&mut xml)
+            .with_context(|| format!("docx document.xml is not valid UTF-8: {}", path.display()))?;
+
+        Ok(strip_xml_tags(&xml))
+    }
+}
+
+/// Crude but dependency-free tag stripper: good enough to turn Word's XML
+/// markup into readable plain text for embedding/search.
+fn strip_xml_tags(xml: &str) -> String {
+    let mut out = String::with_capacity(xml.len());
+    let mut in_tag = false;
+    for c in xml.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Loads the text content of `path` using whichever loader supports its
+/// extension.
+pub fn load_document(path: &Path) -> Result<String> {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    if PlainTextLoader::supports(&ext) {
+        PlainTextLoader::load(path)
+    } else if PdfLoader::supports(&ext) {
+        PdfLoader::load(path)
+    } else if DocxLoader::supports(&ext) {
+        DocxLoader::load(path)
+    } else {
+        anyhow::bail!("No document loader for extension: {}", ext);
+    }
+}
+
+/// One overlapping character window of a document's extracted text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextChunk {
+    pub text: String,
+    pub chunk_index: usize,
+    pub char_start: usize,
+    pub char_end: usize,
+}
+
+/// Splits `text` into overlapping character windows of `window_size` with
+/// `overlap` shared characters between consecutive chunks, so no chunk
+/// boundary silently drops context that an embedding model would need.
+pub fn chunk_text(text: &str, window_size: usize, overlap: usize) -> Vec<TextChunk> {
+    let chars: Vec<char> = text.chars().collect();
+    if chars.is_empty() {
+        return Vec::new();
+    }
+
+    let step = window_size.saturating_sub(overlap).max(1);
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    let mut chunk_index = 0;
+
+    loop {
+        let end = (start + window_size).min(chars.len());
+        chunks.push(TextChunk {
+            text: chars[start..end].iter().collect(),
+            chunk_index,
+            char_start: start,
+            char_end: end,
+        });
+
+        if end == chars.len() {
+            break;
+        }
+        start += step;
+        chunk_index += 1;
+    }
+
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunk_text_splits_with_overlap() {
+        let text = "a".repeat(25);
+        let chunks = chunk_text(&text, 10, 2);
+
+        assert_eq!(chunks[0].char_start, 0);
+        assert_eq!(chunks[0].char_end, 10);
+        assert_eq!(chunks[1].char_start, 8);
+        assert_eq!(chunks[1].char_end, 18);
+        assert_eq!(chunks.last().unwrap().char_end, 25);
+    }
+
+    #[test]
+    fn chunk_text_empty_input_produces_no_chunks() {
+        assert!(chunk_text("", 10, 2).is_empty());
+    }
+
+    #[test]
+    fn chunk_text_shorter_than_window_is_one_chunk() {
+        let chunks = chunk_text("hello", 1000, 200);
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].text, "hello");
+    }
+
+    #[test]
+    fn strip_xml_tags_keeps_only_text_nodes() {
+        let xml = "<w:p><w:r><w:t>Hello World</w:t></w:r></w:p>";
+        assert_eq!(strip_xml_tags(xml), "Hello World");
+    }
+}
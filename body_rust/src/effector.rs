@@ -5,6 +5,8 @@ use serde::{Deserialize, Serialize};
 use std::thread;
 use std::time::Duration;
 
+use crate::config::{ActionStepKind, Config};
+
 /// ActionProposal represents an approved action from the Permission Kernel
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct ActionProposal {
@@ -24,34 +26,77 @@ pub enum ActionPayload {
     PressKey { key: String },
 }
 
+/// The physical input operations an `Effector` needs. The real `Effector`
+/// drives this over `enigo`; tests drive it over a `RecordingSink` that logs
+/// events instead of touching a display server.
+pub trait InputSink {
+    fn type_text(&mut self, text: &str) -> Result<()>;
+    fn move_mouse(&mut self, x: i32, y: i32) -> Result<()>;
+    fn click_button(&mut self, button: Button) -> Result<()>;
+    fn key(&mut self, key: Key, direction: Direction) -> Result<()>;
+}
+
+impl InputSink for Enigo {
+    fn type_text(&mut self, text: &str) -> Result<()> {
+        self.text(text).context("Failed to type text")?;
+        Ok(())
+    }
+
+    fn move_mouse(&mut self, x: i32, y: i32) -> Result<()> {
+        Mouse::move_mouse(self, x, y, enigo::Coordinate::Abs)
+            .context("Failed to move mouse")?;
+        Ok(())
+    }
+
+    fn click_button(&mut self, button: Button) -> Result<()> {
+        self.button(button, Direction::Click)
+            .context("Failed to click mouse")?;
+        Ok(())
+    }
+
+    fn key(&mut self, key: Key, direction: Direction) -> Result<()> {
+        Keyboard::key(self, key, direction).context("Failed to send key")?;
+        Ok(())
+    }
+}
+
 /// Effector executes physical actions on behalf of the agent
-pub struct Effector {
-    enigo: Enigo,
+pub struct Effector<S: InputSink = Enigo> {
+    sink: S,
 }
 
-impl Effector {
-    /// Creates a new Effector instance
+impl Effector<Enigo> {
+    /// Creates a new Effector instance driving the real input device.
     pub fn new() -> Result<Self> {
         let enigo = Enigo::new(&Settings::default())
             .context("Failed to initialize input controller")?;
 
-        Ok(Self { enigo })
+        Ok(Self { sink: enigo })
+    }
+}
+
+impl<S: InputSink> Effector<S> {
+    /// Creates an Effector over an arbitrary `InputSink`, e.g. a
+    /// `RecordingSink` in tests.
+    pub fn with_sink(sink: S) -> Self {
+        Self { sink }
     }
 
-    /// Executes an approved action
+    /// Executes an approved action, blocking the calling thread for the
+    /// action's configured delay plus whatever the dispatched action itself
+    /// sleeps for (e.g. intent pacing after Enter).
     pub fn execute_action(&mut self, action: &ActionProposal) -> Result<()> {
+        let delay_ms = action_delay_ms(action);
+        thread::sleep(Duration::from_millis(delay_ms));
+        self.dispatch_action(action)
+    }
+
+    /// Runs the intent-routed dispatch for `action`, without `execute_action`'s
+    /// leading delay.
+    fn dispatch_action(&mut self, action: &ActionProposal) -> Result<()> {
         println!("[EFFECTOR] ⚡ Executing: {} ({})", action.intent, &action.id[..8]);
         println!("[EFFECTOR]    Domain: {}", action.domain);
 
-        // Extract delay from payload, default to 100ms
-        let delay_ms = action.payload
-            .get("delay_ms")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(100) as u64;
-
-        // Small delay before execution for safety
-        thread::sleep(Duration::from_millis(delay_ms));
-
         // Parse the intent to determine action type
         let intent_upper = action.intent.to_uppercase();
 
@@ -87,8 +132,7 @@ impl Effector {
 
         println!("[EFFECTOR]    Typing: \"{}\"", sanitized);
 
-        self.enigo.text(&sanitized)
-            .context("Failed to type text")?;
+        self.sink.type_text(&sanitized)?;
 
         Ok(())
     }
@@ -114,8 +158,7 @@ impl Effector {
         println!("[EFFECTOR]    Moving mouse to ({}, {})", x, y);
 
         // Move mouse to position
-        self.enigo.move_mouse(x, y, enigo::Coordinate::Abs)
-            .context("Failed to move mouse")?;
+        self.sink.move_mouse(x, y)?;
 
         // Configurable delay for visual feedback
         thread::sleep(Duration::from_millis(delay_ms));
@@ -123,13 +166,69 @@ impl Effector {
         println!("[EFFECTOR]    Clicking left button");
 
         // Click
-        self.enigo.button(Button::Left, Direction::Click)
-            .context("Failed to click mouse")?;
+        self.sink.click_button(Button::Left)?;
 
         Ok(())
     }
 
-    /// Presses a specific key (supports combo keys like "win+r", "ctrl+k")
+    /// Runs a named macro from `config`, executing each step in order and
+    /// sleeping for its `delay_ms` afterward.
+    pub fn execute_macro(&mut self, config: &Config, name: &str) -> Result<()> {
+        let steps = config
+            .get_macro(name)
+            .with_context(|| format!("Unknown macro: {}", name))?;
+
+        println!("[EFFECTOR] ▶ Running macro: {} ({} steps)", name, steps.len());
+
+        for (index, step) in steps.iter().enumerate() {
+            match &step.action {
+                ActionStepKind::TypeText(text) => {
+                    self.execute_type_text(&serde_json::json!({ "text": text }))
+                }
+                ActionStepKind::Click { x, y } => {
+                    self.execute_click(&serde_json::json!({ "x": x, "y": y }))
+                }
+                ActionStepKind::PressKey(key_str) => {
+                    self.execute_press_key(&serde_json::json!({ "key": key_str }))
+                }
+                ActionStepKind::Chord(ops) => self.execute_chord(ops),
+            }
+            .with_context(|| format!("macro '{}' step {} failed", name, index))?;
+
+            if step.delay_ms > 0 {
+                thread::sleep(Duration::from_millis(step.delay_ms));
+            }
+        }
+
+        println!("[EFFECTOR] ✓ Macro complete: {}", name);
+        Ok(())
+    }
+
+    /// Runs a chorded sequence of holds/taps/releases (e.g. hold Ctrl, tap K
+    /// then B, release Ctrl) via a `ModifierState` guard, which releases any
+    /// modifier still held when the sequence ends, whether that's because it
+    /// finished normally or a step returned an error.
+    pub fn execute_chord(&mut self, ops: &[crate::config::ChordOp]) -> Result<()> {
+        use crate::config::ChordOp;
+
+        let mut state = crate::modifier_state::ModifierState::new(&mut self.sink);
+        for op in ops {
+            match op {
+                ChordOp::Hold(key_str) => {
+                    state.press(crate::keybind::Key::parse(key_str).map_err(|e| anyhow::anyhow!(e))?)
+                }
+                ChordOp::Tap(key_str) => {
+                    state.tap(crate::keybind::Key::parse(key_str).map_err(|e| anyhow::anyhow!(e))?)
+                }
+                ChordOp::Release(key_str) => {
+                    state.release(crate::keybind::Key::parse(key_str).map_err(|e| anyhow::anyhow!(e))?)
+                }
+            }?;
+        }
+        Ok(())
+    }
+
+    /// Presses a specific key or chord (e.g. "win+r", "ctrl+k", "ctrl+shift-k")
     pub fn execute_press_key(&mut self, payload: &serde_json::Value) -> Result<()> {
         let key_str = payload
             .get("key")
@@ -138,65 +237,34 @@ impl Effector {
 
         println!("[EFFECTOR]    Pressing key: {}", key_str);
 
-        // Handle combo keys like "win+r", "ctrl+k"
-        if key_str.contains('+') {
-            let parts: Vec<&str> = key_str.split('+').collect();
-            let mut keys_to_press: Vec<Key> = Vec::new();
-
-            for part in &parts {
-                let k = match part.trim().to_uppercase().as_str() {
-                    "WIN" | "GUI" | "META" | "WINDOWS" => Key::Meta,
-                    "CTRL" | "CONTROL" => Key::Control,
-                    "ALT" => Key::Alt,
-                    "SHIFT" => Key::Shift,
-                    s if s.len() == 1 => Key::Unicode(s.chars().next().unwrap()),
-                    other => anyhow::bail!("Unknown combo key part: {}", other),
-                };
-                keys_to_press.push(k);
+        let chord = crate::keybind::parse_keybind_keys(key_str)
+            .with_context(|| format!("Invalid key: {}", key_str))?;
+
+        if chord.len() > 1 {
+            // Holds every key but the last via `ModifierState`, which
+            // guarantees (via its `Drop`) that any modifier it pressed gets
+            // released even if a later key in the combo fails to press or
+            // release — the old press-all-then-release-all-in-reverse code
+            // had no such guarantee and could leave a modifier stuck down.
+            let mut state = crate::modifier_state::ModifierState::new(&mut self.sink);
+            let (held_keys, last_key) = chord.split_at(chord.len() - 1);
+            let last_key = last_key[0];
+
+            for key in held_keys {
+                state.press(*key).context("Failed to press combo key")?;
             }
 
-            // Press all keys down
-            for k in &keys_to_press {
-                self.enigo.key(*k, Direction::Press)
-                    .context("Failed to press combo key")?;
-            }
-            // Release in reverse order
-            for k in keys_to_press.iter().rev() {
-                self.enigo.key(*k, Direction::Release)
-                    .context("Failed to release combo key")?;
+            if crate::modifier_state::is_modifier(&last_key) {
+                state.press(last_key).context("Failed to press combo key")?;
+            } else {
+                state.tap(last_key).context("Failed to tap combo key")?;
             }
 
             return Ok(());
         }
 
-        // Map string to Key enum
-        let key = match key_str.to_uppercase().as_str() {
-            "ENTER" | "RETURN" => Key::Return,
-            "ESC" | "ESCAPE" => Key::Escape,
-            "SPACE" => Key::Space,
-            "TAB" => Key::Tab,
-            "BACKSPACE" => Key::Backspace,
-            "DELETE" => Key::Delete,
-            "LEFT" => Key::LeftArrow,
-            "RIGHT" => Key::RightArrow,
-            "UP" => Key::UpArrow,
-            "DOWN" => Key::DownArrow,
-            "HOME" => Key::Home,
-            "END" => Key::End,
-            "PAGEUP" => Key::PageUp,
-            "PAGEDOWN" => Key::PageDown,
-            "GUI" | "META" | "WINDOWS" => Key::Meta,
-            _ => {
-                // Try to parse as a single character
-                if key_str.len() == 1 {
-                    Key::Unicode(key_str.chars().next().ok_or_else(|| anyhow::anyhow!("Empty key string"))?)
-                } else {
-                    anyhow::bail!("Unknown key: {}", key_str);
-                }
-            }
-        };
-
-        self.enigo.key(key, Direction::Click)
+        let key = to_enigo_key(&chord[0]);
+        self.sink.key(key, Direction::Click)
             .context("Failed to press key")?;
 
         // Intent Pacing: Wait after Enter/Return to allow target app to gain focus
@@ -209,9 +277,60 @@ impl Effector {
     }
 }
 
+/// Maps a parsed `keybind::Key` to the `enigo::Key` used to actually drive
+/// input. Mouse chord members have no keyboard equivalent and are mapped to
+/// the placeholder `Key::Unicode('\0')`; a mouse button inside a key chord
+/// sends no mouse button event.
+pub(crate) fn to_enigo_key(key: &crate::keybind::Key) -> Key {
+    use crate::keybind::Key as K;
+    match key {
+        K::Char(c) => Key::Unicode(*c),
+        K::Return => Key::Return,
+        K::Escape => Key::Escape,
+        K::Tab => Key::Tab,
+        K::Space => Key::Space,
+        K::Backspace => Key::Backspace,
+        K::Delete => Key::Delete,
+        K::Control => Key::Control,
+        K::Shift => Key::Shift,
+        K::Alt => Key::Alt,
+        K::Meta => Key::Meta,
+        K::Left => Key::LeftArrow,
+        K::Right => Key::RightArrow,
+        K::Up => Key::UpArrow,
+        K::Down => Key::DownArrow,
+        K::Home => Key::Home,
+        K::End => Key::End,
+        K::PageUp => Key::PageUp,
+        K::PageDown => Key::PageDown,
+        K::F1 => Key::F1,
+        K::F2 => Key::F2,
+        K::F3 => Key::F3,
+        K::F4 => Key::F4,
+        K::F5 => Key::F5,
+        K::F6 => Key::F6,
+        K::F7 => Key::F7,
+        K::F8 => Key::F8,
+        K::F9 => Key::F9,
+        K::F10 => Key::F10,
+        K::F11 => Key::F11,
+        K::F12 => Key::F12,
+        // Mouse members inside a chord are pressed as a no-op placeholder;
+        // real mouse dispatch happens via `execute_click`.
+        K::Mouse(_) => Key::Unicode('\0'),
+    }
+}
+
 /// Executes a single action from JSON stdin format
 /// Expected format: {"action": "TYPE"|"KEY"|"CLICK", "payload": {...}}
+/// No macro config is passed, so a "MACRO" action returns an error here.
 pub fn execute_action_json(json_str: &str) -> Result<()> {
+    execute_action_json_with_config(json_str, None)
+}
+
+/// Same as `execute_action_json`, but also accepts `"action": "MACRO"` with
+/// `payload: { "name": "..." }` when a macro `Config` is supplied.
+pub fn execute_action_json_with_config(json_str: &str, config: Option<&Config>) -> Result<()> {
     let command: serde_json::Value = serde_json::from_str(json_str)
         .context("Failed to parse JSON command")?;
 
@@ -230,12 +349,29 @@ pub fn execute_action_json(json_str: &str) -> Result<()> {
         "TYPE" => effector.execute_type_text(payload)?,
         "KEY" => effector.execute_press_key(payload)?,
         "CLICK" => effector.execute_click(payload)?,
+        "MACRO" => {
+            let config = config.context("MACRO action requires a loaded macro config")?;
+            let name = payload
+                .get("name")
+                .and_then(|v| v.as_str())
+                .context("Missing 'name' field in MACRO payload")?;
+            effector.execute_macro(config, name)?;
+        }
         _ => anyhow::bail!("Unknown action type: {}", action_type),
     }
 
     Ok(())
 }
 
+/// Extracts the configured delay from an action's payload, default 100ms.
+fn action_delay_ms(action: &ActionProposal) -> u64 {
+    action
+        .payload
+        .get("delay_ms")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(100)
+}
+
 /// Sanitizes text input by removing non-printable control characters
 /// Protects against injection attacks and malformed input
 fn sanitize_text(input: &str) -> String {
@@ -253,13 +389,318 @@ fn sanitize_text(input: &str) -> String {
         .collect()
 }
 
-// NOTE: effector_loop (old HTTP polling approach) removed.
-// Actions now arrive via gRPC StreamActions in main.rs.
+/// Polls `{api_url}/api/actions/pending` for an approved action and
+/// executes it. Runs forever; used by the `effector` subcommand and by
+/// `run_full_mode`'s effector thread while the gate state is `Active`.
+/// Poll failures are logged and retried rather than exiting, matching the
+/// rest of the daemon's graceful-degradation style (e.g.
+/// `notify_kernel_focus` silently ignoring a down Kernel). If the input
+/// controller or the async runtime fails to initialize, the error is
+/// logged and the function returns.
+///
+/// Bootstraps a single-threaded tokio runtime and drives the loop through
+/// `AsyncEffectorAdapter`, so a pending action's delay and the poll itself
+/// yield to the runtime instead of blocking this thread outright — see
+/// `async_effector_loop`.
+pub fn effector_loop(api_url: &str) {
+    let poll_url = format!("{}/api/actions/pending", api_url);
+
+    let effector = match Effector::new() {
+        Ok(effector) => effector,
+        Err(e) => {
+            eprintln!("[EFFECTOR] Failed to initialize input controller: {}", e);
+            return;
+        }
+    };
+
+    let runtime = match tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+    {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("[EFFECTOR] Failed to start async runtime: {}", e);
+            return;
+        }
+    };
+
+    runtime.block_on(async_effector_loop(poll_url, effector));
+}
+
+/// Drives `effector` through an `AsyncEffectorAdapter`: each poll runs on a
+/// `spawn_blocking` task (the HTTP call is blocking `reqwest`), and each
+/// executed action's delay is a `tokio::time::sleep` rather than a blocking
+/// thread sleep. `async_trait` lets `AsyncEffectorAdapter::execute_action`
+/// be called like a regular async method despite going through a trait.
+async fn async_effector_loop(poll_url: String, effector: Effector) {
+    let mut adapter = AsyncEffectorAdapter::new(effector);
+
+    loop {
+        let url = poll_url.clone();
+        let poll_result = tokio::task::spawn_blocking(move || poll_pending_action(&url))
+            .await
+            .context("Poll task panicked")
+            .and_then(|r| r);
+
+        match poll_result {
+            Ok(Some(action)) => {
+                if let Err(e) = adapter.execute_action(&action).await {
+                    eprintln!("[EFFECTOR] Failed to execute {}: {}", action.id, e);
+                }
+            }
+            Ok(None) => tokio::time::sleep(Duration::from_millis(500)).await,
+            Err(e) => {
+                eprintln!("[EFFECTOR] Failed to poll for pending actions: {}", e);
+                tokio::time::sleep(Duration::from_secs(1)).await;
+            }
+        }
+    }
+}
+
+/// A `204 No Content` response means no action is pending right now.
+fn poll_pending_action(poll_url: &str) -> Result<Option<ActionProposal>> {
+    let response = reqwest::blocking::get(poll_url).context("Failed to reach backend")?;
+    if response.status() == reqwest::StatusCode::NO_CONTENT {
+        return Ok(None);
+    }
+    response.json().context("Malformed action response")
+}
+
+/// Async action execution: same behavior as `Effector::execute_action`, but
+/// the delay yields to the Tokio runtime via `tokio::time::sleep` instead of
+/// blocking a worker thread, and the actual enigo calls run on a
+/// `spawn_blocking` task (enigo is not `Send`/async).
+#[async_trait::async_trait]
+trait AsyncEffector {
+    async fn execute_action(&mut self, action: &ActionProposal) -> Result<()>;
+}
+
+/// Adapts a blocking `Effector` to `AsyncEffector`. Holds the `Effector` in
+/// an `Option` so it can be moved into a `spawn_blocking` task and handed
+/// back once that task completes.
+struct AsyncEffectorAdapter {
+    effector: Option<Effector>,
+}
+
+impl AsyncEffectorAdapter {
+    fn new(effector: Effector) -> Self {
+        Self { effector: Some(effector) }
+    }
+}
+
+#[async_trait::async_trait]
+impl AsyncEffector for AsyncEffectorAdapter {
+    async fn execute_action(&mut self, action: &ActionProposal) -> Result<()> {
+        tokio::time::sleep(Duration::from_millis(action_delay_ms(action))).await;
+
+        let mut effector = self
+            .effector
+            .take()
+            .context("Effector is already executing another action")?;
+        let action = action.clone();
+
+        let (effector, result) = tokio::task::spawn_blocking(move || {
+            let result = effector.dispatch_action(&action);
+            (effector, result)
+        })
+        .await
+        .context("Effector task panicked")?;
+
+        self.effector = Some(effector);
+        result
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// One physical input event as seen by an `InputSink`, in emission order.
+    #[derive(Debug, Clone, PartialEq)]
+    enum RecordedEvent {
+        TypeText(String),
+        MoveMouse(i32, i32),
+        Click(Button),
+        Key(Key, Direction),
+    }
+
+    /// An `InputSink` that logs events instead of driving the OS, so the
+    /// effector's intent-dispatch and combo-key logic can be asserted
+    /// deterministically without a display server.
+    #[derive(Default)]
+    struct RecordingSink {
+        events: Vec<RecordedEvent>,
+    }
+
+    impl InputSink for RecordingSink {
+        fn type_text(&mut self, text: &str) -> Result<()> {
+            self.events.push(RecordedEvent::TypeText(text.to_string()));
+            Ok(())
+        }
+
+        fn move_mouse(&mut self, x: i32, y: i32) -> Result<()> {
+            self.events.push(RecordedEvent::MoveMouse(x, y));
+            Ok(())
+        }
+
+        fn click_button(&mut self, button: Button) -> Result<()> {
+            self.events.push(RecordedEvent::Click(button));
+            Ok(())
+        }
+
+        fn key(&mut self, key: Key, direction: Direction) -> Result<()> {
+            self.events.push(RecordedEvent::Key(key, direction));
+            Ok(())
+        }
+    }
+
+    fn recording_effector() -> Effector<RecordingSink> {
+        Effector::with_sink(RecordingSink::default())
+    }
+
+    /// An `InputSink` that behaves like `RecordingSink`, except the first
+    /// call matching `fail_on` returns an error instead of recording an
+    /// event. Lets a test simulate a combo step failing mid-sequence.
+    #[derive(Default)]
+    struct FailingSink {
+        events: Vec<RecordedEvent>,
+        fail_on: Option<(Key, Direction)>,
+    }
+
+    impl InputSink for FailingSink {
+        fn type_text(&mut self, text: &str) -> Result<()> {
+            self.events.push(RecordedEvent::TypeText(text.to_string()));
+            Ok(())
+        }
+
+        fn move_mouse(&mut self, x: i32, y: i32) -> Result<()> {
+            self.events.push(RecordedEvent::MoveMouse(x, y));
+            Ok(())
+        }
+
+        fn click_button(&mut self, button: Button) -> Result<()> {
+            self.events.push(RecordedEvent::Click(button));
+            Ok(())
+        }
+
+        fn key(&mut self, key: Key, direction: Direction) -> Result<()> {
+            if self.fail_on == Some((key, direction)) {
+                anyhow::bail!("simulated failure on {:?} {:?}", key, direction);
+            }
+            self.events.push(RecordedEvent::Key(key, direction));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn execute_type_text_records_sanitized_text() {
+        let mut effector = recording_effector();
+        effector
+            .execute_type_text(&serde_json::json!({ "text": "Hello\x00World" }))
+            .unwrap();
+
+        assert_eq!(
+            effector.sink.events,
+            vec![RecordedEvent::TypeText("HelloWorld".to_string())]
+        );
+    }
+
+    #[test]
+    fn execute_click_records_move_then_click() {
+        let mut effector = recording_effector();
+        effector
+            .execute_click(&serde_json::json!({ "x": 10, "y": 20, "delay_ms": 0 }))
+            .unwrap();
+
+        assert_eq!(
+            effector.sink.events,
+            vec![
+                RecordedEvent::MoveMouse(10, 20),
+                RecordedEvent::Click(Button::Left),
+            ]
+        );
+    }
+
+    #[test]
+    fn execute_press_key_records_single_key_click() {
+        let mut effector = recording_effector();
+        effector
+            .execute_press_key(&serde_json::json!({ "key": "a" }))
+            .unwrap();
+
+        assert_eq!(
+            effector.sink.events,
+            vec![RecordedEvent::Key(Key::Unicode('a'), Direction::Click)]
+        );
+    }
+
+    #[test]
+    fn execute_press_key_combo_holds_modifier_and_taps_final_key() {
+        let mut effector = recording_effector();
+        effector
+            .execute_press_key(&serde_json::json!({ "key": "ctrl+k" }))
+            .unwrap();
+
+        assert_eq!(
+            effector.sink.events,
+            vec![
+                RecordedEvent::Key(Key::Control, Direction::Press),
+                RecordedEvent::Key(Key::Unicode('k'), Direction::Click),
+                RecordedEvent::Key(Key::Control, Direction::Release),
+            ]
+        );
+    }
+
+    #[test]
+    fn execute_press_key_combo_recovers_stuck_modifier_on_failure() {
+        let mut effector = Effector::with_sink(FailingSink {
+            events: Vec::new(),
+            fail_on: Some((Key::Unicode('k'), Direction::Click)),
+        });
+
+        let result = effector.execute_press_key(&serde_json::json!({ "key": "ctrl+k" }));
+
+        assert!(result.is_err(), "the simulated tap failure should propagate");
+        assert_eq!(
+            effector.sink.events,
+            vec![
+                RecordedEvent::Key(Key::Control, Direction::Press),
+                RecordedEvent::Key(Key::Control, Direction::Release),
+            ],
+            "Control must still be released even though tapping 'k' failed mid-combo"
+        );
+    }
+
+    #[test]
+    fn execute_macro_records_full_step_timeline() {
+        let config = Config {
+            macros: std::collections::HashMap::from([(
+                "greet".to_string(),
+                vec![
+                    crate::config::ActionStep {
+                        action: ActionStepKind::TypeText("hi".to_string()),
+                        delay_ms: 0,
+                    },
+                    crate::config::ActionStep {
+                        action: ActionStepKind::PressKey("enter".to_string()),
+                        delay_ms: 0,
+                    },
+                ],
+            )]),
+        };
+
+        let mut effector = recording_effector();
+        effector.execute_macro(&config, "greet").unwrap();
+
+        assert_eq!(
+            effector.sink.events,
+            vec![
+                RecordedEvent::TypeText("hi".to_string()),
+                RecordedEvent::Key(Key::Return, Direction::Click),
+            ]
+        );
+    }
+
     #[test]
     fn test_sanitize_text_removes_null_bytes() {
         let input = "Hello\x00World";
@@ -0,0 +1,119 @@
+// Author: Enkae (enkae.dev@pm.me)
+//! External hook/plugin subsystem, borrowed from xplr's runner model:
+//! operators register shell commands to run on focus changes and
+//! `AppState` transitions. Each hook gets event context via environment
+//! variables (`GHOST_FOCUS_NAME`, `GHOST_STATE`, ...) instead of
+//! command-line arguments, and its stdout is parsed as optional action
+//! JSON that callers feed back through the effector.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// Hook commands keyed by event: `on_focus` runs whenever the focused
+/// element changes, `on_state_change` whenever `AppState` transitions.
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub struct HookConfig {
+    #[serde(default)]
+    pub on_focus: Vec<String>,
+    #[serde(default)]
+    pub on_state_change: Vec<String>,
+}
+
+impl HookConfig {
+    /// Loads and parses a hook config file. Format is chosen from the file
+    /// extension, same as `config::Config::load`: `.ron` for RON,
+    /// `.json5`/`.json` for JSON5.
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read hook config: {}", path.display()))?;
+
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("ron") => ron::from_str(&raw)
+                .with_context(|| format!("Failed to parse RON hook config: {}", path.display())),
+            Some("json5") | Some("json") => json5::from_str(&raw)
+                .with_context(|| format!("Failed to parse JSON5 hook config: {}", path.display())),
+            other => bail!(
+                "Unsupported hook config extension {:?} for {}; expected .ron or .json5",
+                other,
+                path.display()
+            ),
+        }
+    }
+}
+
+/// Event context injected into a hook's environment. A field left `None`
+/// is simply not set, so e.g. an `on_state_change` hook doesn't see a
+/// stale `GHOST_FOCUS_NAME` left over from the last focus event.
+#[derive(Debug, Clone, Default)]
+pub struct HookContext {
+    pub focus_name: Option<String>,
+    pub control_type: Option<String>,
+    pub bounding_rect: Option<String>,
+    pub state: Option<String>,
+    pub session_path: Option<String>,
+}
+
+impl HookContext {
+    fn apply_env(&self, command: &mut Command) {
+        if let Some(v) = &self.focus_name {
+            command.env("GHOST_FOCUS_NAME", v);
+        }
+        if let Some(v) = &self.control_type {
+            command.env("GHOST_CONTROL_TYPE", v);
+        }
+        if let Some(v) = &self.bounding_rect {
+            command.env("GHOST_BOUNDING_RECT", v);
+        }
+        if let Some(v) = &self.state {
+            command.env("GHOST_STATE", v);
+        }
+        if let Some(v) = &self.session_path {
+            command.env("GHOST_SESSION_PATH", v);
+        }
+    }
+}
+
+/// Runs every entry in `commands` with `context` injected as environment
+/// variables, parsing each hook's stdout as optional action JSON. Returns
+/// the JSON strings of any actions the hooks proposed, in the order the
+/// hooks ran; a hook with empty or non-JSON stdout proposes nothing. A
+/// hook that fails to spawn or exits non-zero is logged and skipped
+/// rather than aborting the remaining hooks.
+pub fn run_hooks(commands: &[String], context: &HookContext) -> Vec<String> {
+    let mut actions = Vec::new();
+
+    for command_line in commands {
+        match spawn_hook(command_line, context) {
+            Ok(output) => {
+                if !output.status.success() {
+                    eprintln!("[HOOKS] '{}' exited with {}", command_line, output.status);
+                    continue;
+                }
+
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                let trimmed = stdout.trim();
+                if !trimmed.is_empty() && serde_json::from_str::<serde_json::Value>(trimmed).is_ok() {
+                    actions.push(trimmed.to_string());
+                }
+            }
+            Err(e) => eprintln!("[HOOKS] Failed to run '{}': {}", command_line, e),
+        }
+    }
+
+    actions
+}
+
+fn spawn_hook(command_line: &str, context: &HookContext) -> Result<Output> {
+    let mut parts = command_line.split_whitespace();
+    let program = parts.next().context("Empty hook command")?;
+
+    let mut command = Command::new(program);
+    command.args(parts);
+    context.apply_env(&mut command);
+
+    command
+        .output()
+        .with_context(|| format!("Failed to spawn hook: {}", command_line))
+}
@@ -0,0 +1,166 @@
+// Author: Enkae (enkae.dev@pm.me)
+//! Support types for the Librarian's indexing job: a cooperative
+//! cancellation flag, a progress snapshot shape, and a checkpoint of
+//! pending/in-flight/completed paths so an interrupted index can resume
+//! instead of restarting from scratch.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+
+/// Set once the process has been asked to shut down gracefully (Ctrl-C, or
+/// an `AppState::Paused` transition the caller wants to honor immediately).
+/// Every `CancellationToken`, including ones already handed out, reports
+/// cancelled from this point on — there's no "undo" because the process is
+/// on its way out.
+static SHUTDOWN_REQUESTED: OnceLock<Arc<AtomicBool>> = OnceLock::new();
+
+fn shutdown_flag() -> &'static Arc<AtomicBool> {
+    SHUTDOWN_REQUESTED.get_or_init(|| Arc::new(AtomicBool::new(false)))
+}
+
+/// Marks the process as shutting down, so any running index job checkpoints
+/// and unwinds instead of being killed mid-write. Safe to call more than
+/// once (e.g. a Ctrl-C handler that fires twice).
+pub fn request_shutdown() {
+    shutdown_flag().store(true, Ordering::SeqCst);
+}
+
+/// A cheaply-cloneable flag workers poll between files to stop early.
+/// Cancellation is cooperative: in-flight work finishes, but no new file is
+/// started once it's set. Also reports cancelled once `request_shutdown` has
+/// been called, so a process-wide shutdown stops every outstanding job
+/// without each caller having to plumb its own signal through.
+#[derive(Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self(Arc::new(AtomicBool::new(false)))
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst) || shutdown_flag().load(Ordering::SeqCst)
+    }
+}
+
+/// A point-in-time snapshot of an indexing job's progress, sent down a
+/// channel the caller can subscribe to instead of blocking on completion.
+#[derive(Debug, Clone, Default)]
+pub struct IndexProgress {
+    pub files_seen: u64,
+    pub files_indexed: u64,
+    pub bytes_indexed: u64,
+    pub current_path: Option<PathBuf>,
+}
+
+/// A directory scan's resumable state: files still to index, files the
+/// worker pool had open when the checkpoint was written, and files already
+/// done. Persisted as MessagePack so a crash or a graceful shutdown loses at
+/// most the files that were `in_flight`, not the whole job.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub pending: VecDeque<PathBuf>,
+    pub completed: HashSet<PathBuf>,
+    /// Plural, not `Option<PathBuf>`, because the index job's worker pool
+    /// (`INDEX_WORKER_THREADS`) indexes several files concurrently; every
+    /// path a worker had open at checkpoint time lands here.
+    pub in_flight: Vec<PathBuf>,
+}
+
+impl Checkpoint {
+    /// Loads the checkpoint for `root`, or an empty one if none exists yet
+    /// (first run, or a previous run completed and cleared it). Any path
+    /// left `in_flight` by an interrupted run is re-enqueued to the front of
+    /// `pending`, since it may or may not have finished uploading.
+    pub fn load(root: &Path) -> Self {
+        let checkpoint_path = Self::file_path(root);
+        let mut checkpoint: Self = std::fs::read(&checkpoint_path)
+            .ok()
+            .and_then(|bytes| rmp_serde::from_slice(&bytes).ok())
+            .unwrap_or_default();
+
+        if !checkpoint.in_flight.is_empty() {
+            for path in checkpoint.in_flight.drain(..).rev() {
+                checkpoint.pending.push_front(path);
+            }
+        }
+
+        checkpoint
+    }
+
+    /// Persists this checkpoint so a cancelled or crashed job can resume.
+    pub fn save(&self, root: &Path) -> Result<()> {
+        let checkpoint_path = Self::file_path(root);
+        let contents = rmp_serde::to_vec(self).context("Failed to serialize checkpoint")?;
+        std::fs::write(&checkpoint_path, contents)
+            .with_context(|| format!("Failed to write checkpoint: {}", checkpoint_path.display()))
+    }
+
+    /// Removes `root`'s checkpoint once a job completes without being
+    /// cancelled, so the next run starts fresh rather than "resuming" an
+    /// already-finished index forever.
+    pub fn clear(root: &Path) -> Result<()> {
+        let checkpoint_path = Self::file_path(root);
+        match std::fs::remove_file(&checkpoint_path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e).with_context(|| {
+                format!("Failed to remove checkpoint: {}", checkpoint_path.display())
+            }),
+        }
+    }
+
+    /// Checkpoints live alongside temp files, one per root, named from a
+    /// content hash of the root path so two watched roots never collide.
+    fn file_path(root: &Path) -> PathBuf {
+        let digest = blake3::hash(root.to_string_lossy().as_bytes()).to_hex();
+        std::env::temp_dir().join(format!("ghost-index-checkpoint-{}.msgpack", digest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_re_enqueues_in_flight_paths_ahead_of_pending_in_original_order() {
+        let root = std::env::temp_dir().join("ghost_index_job_test_root");
+
+        let mut checkpoint = Checkpoint::default();
+        checkpoint.pending.push_back(PathBuf::from("old1.txt"));
+        checkpoint.pending.push_back(PathBuf::from("old2.txt"));
+        checkpoint.completed.insert(PathBuf::from("done.txt"));
+        checkpoint.in_flight = vec![
+            PathBuf::from("a.txt"),
+            PathBuf::from("b.txt"),
+            PathBuf::from("c.txt"),
+        ];
+        checkpoint.save(&root).unwrap();
+
+        let resumed = Checkpoint::load(&root);
+
+        assert_eq!(
+            resumed.pending,
+            VecDeque::from(vec![
+                PathBuf::from("a.txt"),
+                PathBuf::from("b.txt"),
+                PathBuf::from("c.txt"),
+                PathBuf::from("old1.txt"),
+                PathBuf::from("old2.txt"),
+            ]),
+            "in_flight paths must be resumed ahead of pending, in the order they were in_flight"
+        );
+        assert!(resumed.in_flight.is_empty());
+        assert!(resumed.completed.contains(&PathBuf::from("done.txt")));
+
+        Checkpoint::clear(&root).unwrap();
+    }
+}
@@ -0,0 +1,106 @@
+// Author: Enkae (enkae.dev@pm.me)
+//! Named-pipe transport for the hybrid daemon, modeled on xplr's session
+//! pipes: a session directory holding one FIFO per typed channel (commands
+//! in, focus updates out, scan results out, action results out), so
+//! consumers read structured channels instead of parsing stdout prefixes.
+//! Unix-only for now — Windows named pipes are a fundamentally different
+//! API (`CreateNamedPipeW`, not a filesystem FIFO) and aren't wired up yet.
+
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+const MSG_IN: &str = "msg_in";
+const FOCUS_OUT: &str = "focus_out";
+const SCAN_OUT: &str = "scan_out";
+const RESULT_OUT: &str = "result_out";
+
+/// Where the active session directory's path is published, so a consumer
+/// that didn't launch the daemon itself can still find it.
+fn session_pointer_path() -> PathBuf {
+    std::env::temp_dir().join("ghost-session-path")
+}
+
+/// A session directory of FIFOs for the hybrid daemon's typed IPC
+/// channels. `close` removes the directory explicitly; a crashed daemon
+/// leaves it behind for postmortem instead of being cleaned up on drop.
+pub struct IpcSession {
+    dir: PathBuf,
+}
+
+impl IpcSession {
+    /// Creates a fresh session directory under the system temp dir, named
+    /// uniquely from the daemon's pid, mkfifo's all four channels inside
+    /// it, and publishes the directory path for other processes to find.
+    #[cfg(unix)]
+    pub fn create() -> Result<Self> {
+        let dir = std::env::temp_dir().join(format!("ghost-session-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create session dir: {}", dir.display()))?;
+
+        for name in [MSG_IN, FOCUS_OUT, SCAN_OUT, RESULT_OUT] {
+            mkfifo(&dir.join(name))?;
+        }
+
+        std::fs::write(session_pointer_path(), dir.to_string_lossy().as_bytes())
+            .context("Failed to publish session pointer")?;
+
+        Ok(Self { dir })
+    }
+
+    #[cfg(not(unix))]
+    pub fn create() -> Result<Self> {
+        anyhow::bail!("Named-pipe IPC sessions are not implemented on this platform yet")
+    }
+
+    pub fn msg_in(&self) -> PathBuf {
+        self.dir.join(MSG_IN)
+    }
+
+    pub fn focus_out(&self) -> PathBuf {
+        self.dir.join(FOCUS_OUT)
+    }
+
+    pub fn scan_out(&self) -> PathBuf {
+        self.dir.join(SCAN_OUT)
+    }
+
+    pub fn result_out(&self) -> PathBuf {
+        self.dir.join(RESULT_OUT)
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Removes the session directory and its FIFOs.
+    pub fn close(self) -> Result<()> {
+        std::fs::remove_dir_all(&self.dir)
+            .with_context(|| format!("Failed to remove session dir: {}", self.dir.display()))
+    }
+}
+
+#[cfg(unix)]
+fn mkfifo(path: &Path) -> Result<()> {
+    use std::ffi::CString;
+
+    let path_c = CString::new(path.to_string_lossy().as_bytes())
+        .context("Session pipe path contains a NUL byte")?;
+    let result = unsafe { libc::mkfifo(path_c.as_ptr(), 0o600) };
+    if result != 0 {
+        return Err(std::io::Error::last_os_error())
+            .with_context(|| format!("Failed to create FIFO: {}", path.display()));
+    }
+    Ok(())
+}
+
+/// Opens `path` for writing, blocking until a reader connects — the normal
+/// behavior for a FIFO opened write-only.
+pub fn open_writer(path: &Path) -> Result<File> {
+    File::create(path).with_context(|| format!("Failed to open writer for {}", path.display()))
+}
+
+/// Opens `path` for reading, blocking until a writer connects.
+pub fn open_reader(path: &Path) -> Result<File> {
+    File::open(path).with_context(|| format!("Failed to open reader for {}", path.display()))
+}
@@ -0,0 +1,263 @@
+// Author: Enkae (enkae.dev@pm.me)
+//! A first-class `Key` type and chord parser.
+//!
+//! This replaces the ad-hoc `match key_str.to_uppercase()` that used to live
+//! directly in `effector::execute_press_key`, which only covered a handful
+//! of names, duplicated its mapping between the combo and single-key paths,
+//! and had no F-keys or mouse buttons. Every variant here lists its
+//! case-insensitive aliases and a canonical `config_name()`, so the macro
+//! config loader and the live key-press path share one validated vocabulary.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// A mouse button, usable inside a chord (e.g. held modifier + click).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+}
+
+impl MouseButton {
+    fn aliases(&self) -> &'static [&'static str] {
+        match self {
+            MouseButton::Left => &["MOUSELEFT", "LMB"],
+            MouseButton::Right => &["MOUSERIGHT", "RMB"],
+            MouseButton::Middle => &["MOUSEMIDDLE", "MMB"],
+        }
+    }
+}
+
+/// A single key on the keyboard, or a mouse button, as it can appear in a
+/// chord like `"ctrl+shift+k"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Key {
+    Char(char),
+    Return,
+    Escape,
+    Tab,
+    Space,
+    Backspace,
+    Delete,
+    Control,
+    Shift,
+    Alt,
+    Meta,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    Mouse(MouseButton),
+}
+
+/// All named variants (i.e. everything but `Key::Char`, which is unbounded),
+/// in a fixed order so the full keyspace can be enumerated and validated.
+const ALL_NAMED: &[Key] = &[
+    Key::Return,
+    Key::Escape,
+    Key::Tab,
+    Key::Space,
+    Key::Backspace,
+    Key::Delete,
+    Key::Control,
+    Key::Shift,
+    Key::Alt,
+    Key::Meta,
+    Key::Left,
+    Key::Right,
+    Key::Up,
+    Key::Down,
+    Key::Home,
+    Key::End,
+    Key::PageUp,
+    Key::PageDown,
+    Key::F1,
+    Key::F2,
+    Key::F3,
+    Key::F4,
+    Key::F5,
+    Key::F6,
+    Key::F7,
+    Key::F8,
+    Key::F9,
+    Key::F10,
+    Key::F11,
+    Key::F12,
+    Key::Mouse(MouseButton::Left),
+    Key::Mouse(MouseButton::Right),
+    Key::Mouse(MouseButton::Middle),
+];
+
+impl Key {
+    /// Iterate over every named variant (not `Char`, which is unbounded).
+    pub fn iter() -> impl Iterator<Item = Key> {
+        ALL_NAMED.iter().copied()
+    }
+
+    /// Case-insensitive aliases accepted for this key, in canonical-first
+    /// order. `Char` has no fixed aliases; any single character works.
+    pub fn aliases(&self) -> &'static [&'static str] {
+        match self {
+            Key::Char(_) => &[],
+            Key::Return => &["ENTER", "RETURN"],
+            Key::Escape => &["ESCAPE", "ESC"],
+            Key::Tab => &["TAB"],
+            Key::Space => &["SPACE"],
+            Key::Backspace => &["BACKSPACE"],
+            Key::Delete => &["DELETE", "DEL"],
+            Key::Control => &["CONTROL", "CTRL"],
+            Key::Shift => &["SHIFT"],
+            Key::Alt => &["ALT"],
+            Key::Meta => &["META", "WIN", "GUI", "WINDOWS"],
+            Key::Left => &["LEFT"],
+            Key::Right => &["RIGHT"],
+            Key::Up => &["UP"],
+            Key::Down => &["DOWN"],
+            Key::Home => &["HOME"],
+            Key::End => &["END"],
+            Key::PageUp => &["PAGEUP", "PGUP"],
+            Key::PageDown => &["PAGEDOWN", "PGDN"],
+            Key::F1 => &["F1"],
+            Key::F2 => &["F2"],
+            Key::F3 => &["F3"],
+            Key::F4 => &["F4"],
+            Key::F5 => &["F5"],
+            Key::F6 => &["F6"],
+            Key::F7 => &["F7"],
+            Key::F8 => &["F8"],
+            Key::F9 => &["F9"],
+            Key::F10 => &["F10"],
+            Key::F11 => &["F11"],
+            Key::F12 => &["F12"],
+            Key::Mouse(button) => button.aliases(),
+        }
+    }
+
+    /// The canonical alias for this key, used when re-serializing a parsed
+    /// chord back into config form.
+    pub fn config_name(&self) -> &'static str {
+        match self {
+            Key::Char(_) => "",
+            other => other.aliases()[0],
+        }
+    }
+
+    /// Parses a single key token (not a chord). Matching is case-insensitive;
+    /// a lone character falls back to `Key::Char`.
+    pub fn parse(token: &str) -> Result<Key, ParseError> {
+        let normalized = token.trim().to_uppercase();
+        if normalized.is_empty() {
+            return Err(ParseError { token: token.to_string() });
+        }
+
+        for key in Key::iter() {
+            if key.aliases().contains(&normalized.as_str()) {
+                return Ok(key);
+            }
+        }
+
+        let mut chars = token.trim().chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return Ok(Key::Char(c));
+        }
+
+        Err(ParseError { token: token.to_string() })
+    }
+}
+
+impl FromStr for Key {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Key::parse(s)
+    }
+}
+
+/// Error returned when a chord contains a token that doesn't resolve to a
+/// known `Key`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub token: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unknown key: '{}'", self.token)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Splits a chord string on both `'+'` and `'-'` (so `"ctrl+shift-k"` and
+/// `"ctrl-shift+k"` both work) and parses each part into a `Key`.
+pub fn parse_keybind_keys(chord: &str) -> Result<Vec<Key>, ParseError> {
+    chord
+        .split(['+', '-'])
+        .map(Key::parse)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_case_insensitively() {
+        for token in ["Enter", "ENTER", "enter", "Return", "RETURN"] {
+            assert_eq!(Key::parse(token).unwrap(), Key::Return);
+        }
+    }
+
+    #[test]
+    fn parses_meta_aliases() {
+        for token in ["win", "GUI", "Meta", "windows"] {
+            assert_eq!(Key::parse(token).unwrap(), Key::Meta);
+        }
+    }
+
+    #[test]
+    fn parses_f_keys() {
+        assert_eq!(Key::parse("F5").unwrap(), Key::F5);
+        assert_eq!(Key::parse("f12").unwrap(), Key::F12);
+    }
+
+    #[test]
+    fn parses_single_char() {
+        assert_eq!(Key::parse("k").unwrap(), Key::Char('k'));
+    }
+
+    #[test]
+    fn rejects_unknown_token() {
+        let err = Key::parse("notakey").unwrap_err();
+        assert_eq!(err.token, "notakey");
+    }
+
+    #[test]
+    fn chord_splits_on_plus_and_dash() {
+        let chord = parse_keybind_keys("ctrl+shift-k").unwrap();
+        assert_eq!(chord, vec![Key::Control, Key::Shift, Key::Char('k')]);
+    }
+
+    #[test]
+    fn chord_reports_bad_token() {
+        let err = parse_keybind_keys("ctrl+bogus").unwrap_err();
+        assert_eq!(err.token, "bogus");
+    }
+}
@@ -1,14 +1,51 @@
 // Author: Enkae (enkae.dev@pm.me)
+use crate::artifact_queue::ArtifactQueue;
+use crate::index_job::{CancellationToken, Checkpoint, IndexProgress};
+use crate::match_list::MatchList;
+use crate::semantic_index::{Embedder, RemoteEmbedder, VectorStore};
 use anyhow::{Context, Result};
 use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
 use std::sync::{Arc, Mutex};
-use std::time::SystemTime;
+use std::time::{Duration, Instant, SystemTime};
 use walkdir::WalkDir;
 
+/// Files larger than this are skipped regardless of the match list.
+const MAX_INDEXABLE_BYTES: u64 = 100 * 1024 * 1024;
+
+/// Worker threads in the initial-index job's pool. Bounded so indexing a
+/// huge tree can't starve the rest of the process (the file watcher, the
+/// hybrid daemon's stdin loop).
+const INDEX_WORKER_THREADS: usize = 4;
+
+/// Save a checkpoint after this many files finish, so a crash loses at most
+/// a small batch of already-completed work instead of the whole job.
+const CHECKPOINT_EVERY_FILES: u64 = 50;
+
+/// ...or after this much wall-clock time, whichever comes first, so a slow
+/// tree (large files, a flaky network upload) still gets checkpointed even
+/// if it hasn't finished `CHECKPOINT_EVERY_FILES` files yet.
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// How long a path must go quiet before its pending change is flushed.
+/// Collapses bursts like an editor's several writes per save, or a large
+/// unzip, into a single re-index per file.
+const DEBOUNCE_WINDOW: std::time::Duration = std::time::Duration::from_millis(250);
+
+/// What happened to a path since its last flush. `Create` and `Write` fold
+/// into the same re-index action; a later `Remove` overrides either.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChangeKind {
+    Create,
+    Write,
+    Remove,
+}
+
 /// FileEntry represents an indexed file in the Librarian's memory
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileEntry {
@@ -17,195 +54,287 @@ pub struct FileEntry {
     pub extension: Option<String>,
     pub size_bytes: u64,
     pub modified: SystemTime,
+    /// BLAKE3 digest of the file's content, if hashing succeeded. Used to
+    /// skip re-sending touch-only saves and to detect exact duplicates
+    /// across watched directories.
+    pub content_hash: Option<String>,
 }
 
 /// Librarian indexes and watches file system for semantic file search
 pub struct Librarian {
     /// In-memory file index (path -> FileEntry)
     index: Arc<Mutex<HashMap<PathBuf, FileEntry>>>,
+    /// Reverse content-hash index (digest -> every path with that digest),
+    /// used to tag artifacts as duplicates of one another.
+    duplicates: Arc<Mutex<HashMap<String, Vec<PathBuf>>>>,
     /// Directories being watched
     watched_dirs: Vec<PathBuf>,
-    /// API endpoint to submit file artifacts
-    api_url: String,
+    /// Non-blocking handle to the batched artifact submission subsystem.
+    artifact_queue: ArtifactQueue,
+    /// Compiled include/exclude patterns, shared by the initial walk and
+    /// the event handler so both apply identical skip semantics. Extended
+    /// with each watched root's `.ghostignore` as it's added.
+    matcher: MatchList,
+    /// Chunk embeddings for every indexed document, queried by the hybrid
+    /// daemon's `SEARCH` command. Cheap to clone (shares the underlying
+    /// store), so the file watcher's event handler can hold its own handle.
+    semantic_index: VectorStore,
+    /// Produces the vectors `semantic_index` stores.
+    embedder: Arc<dyn Embedder>,
 }
 
 impl Librarian {
-    /// Create a new Librarian instance
-    pub fn new(api_url: String) -> Self {
+    /// Create a new Librarian instance. `matcher` is the base include/
+    /// exclude pattern list (e.g. `MatchList::defaults()`), before any
+    /// per-root `.ghostignore` is layered on in `watch_directory`. Spawns
+    /// the background artifact flusher that POSTs to `{api_url}/batch`.
+    /// `embeddings_endpoint` is where the default `RemoteEmbedder` sends
+    /// chunks to be embedded.
+    pub fn new(api_url: String, embeddings_endpoint: String, matcher: MatchList) -> Self {
         Self {
             index: Arc::new(Mutex::new(HashMap::new())),
+            duplicates: Arc::new(Mutex::new(HashMap::new())),
             watched_dirs: Vec::new(),
-            api_url,
+            artifact_queue: ArtifactQueue::start(api_url),
+            matcher,
+            semantic_index: VectorStore::new(),
+            embedder: Arc::new(RemoteEmbedder::new(embeddings_endpoint)),
         }
     }
 
-    /// Add a directory to watch and index
-    pub fn watch_directory(&mut self, path: PathBuf) -> Result<()> {
+    /// The semantic index's query interface, for the hybrid daemon's
+    /// `SEARCH` command: embeds `query` and returns the `top_k` most
+    /// cosine-similar chunks across every indexed document.
+    pub fn search(&self, query: &str, top_k: usize) -> Result<Vec<(f32, crate::semantic_index::VectorEntry)>> {
+        let query_vector = self.embedder.embed(query)?;
+        Ok(self.semantic_index.search(&query_vector, top_k))
+    }
+
+    /// Add a directory to watch and index. `cancel` lets the caller stop the
+    /// initial index early (e.g. on Ctrl-C or an `AppState::Paused`
+    /// transition) and still have it checkpoint instead of being killed.
+    pub fn watch_directory(&mut self, path: PathBuf, cancel: &CancellationToken) -> Result<()> {
         println!("[LIBRARIAN] Adding watch directory: {}", path.display());
 
+        self.matcher = self.matcher.extended_with_ignore_file(&path);
+
         // Initial index of the directory
-        self.index_directory(&path)?;
+        self.index_directory(&path, cancel)?;
 
         self.watched_dirs.push(path);
         Ok(())
     }
 
-    /// Index all files in a directory recursively
-    pub fn index_directory(&self, path: &Path) -> Result<()> {
-        println!("[LIBRARIAN] Indexing directory: {}", path.display());
-
-        let mut count = 0;
-
-        for entry in WalkDir::new(path)
-            .follow_links(false)
-            .into_iter()
-            .filter_map(|e| e.ok())
-        {
-            // Skip directories, only index files
-            if !entry.file_type().is_file() {
-                continue;
-            }
-
-            let path = entry.path().to_path_buf();
-
-            // Skip hidden files and common exclusions
-            if self.should_skip(&path) {
-                continue;
-            }
-
-            match self.create_file_entry(&path) {
-                Ok(file_entry) => {
-                    let mut index = self.index.lock().unwrap();
-                    index.insert(path.clone(), file_entry.clone());
-                    count += 1;
-
-                    // Send to Go backend as artifact
-                    if let Err(e) = self.send_file_artifact(&file_entry) {
-                        eprintln!("[LIBRARIAN] Failed to send artifact: {}", e);
-                    }
-                }
-                Err(e) => {
-                    eprintln!("[LIBRARIAN] Failed to index {}: {}", path.display(), e);
-                }
-            }
-        }
-
-        println!("[LIBRARIAN] Indexed {} files from {}", count, path.display());
+    /// Indexes `path` synchronously: runs `index_directory_job` to
+    /// completion (or cancellation) and discards its progress stream. Kept
+    /// for the simple initial-index-on-watch call site; subscribe to
+    /// `index_directory_job`'s receiver directly for progress or
+    /// cancellation.
+    pub fn index_directory(&self, path: &Path, cancel: &CancellationToken) -> Result<()> {
+        let progress_rx = self.index_directory_job(path, cancel.clone())?;
+        for _progress in progress_rx {}
         Ok(())
     }
 
-    /// Create a FileEntry from a path
-    fn create_file_entry(&self, path: &Path) -> Result<FileEntry> {
-        let metadata = std::fs::metadata(path)
-            .context("Failed to read file metadata")?;
-
-        let file_name = path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("unknown")
-            .to_string();
-
-        let extension = path
-            .extension()
-            .and_then(|e| e.to_str())
-            .map(|s| s.to_string());
+    /// Starts indexing `path` as a cancellable, parallel background job and
+    /// returns immediately with a channel of `IndexProgress` snapshots. The
+    /// job resumes from any checkpoint left by a previous cancelled run of
+    /// the same root, and checkpoints its own progress if `cancel` fires.
+    pub fn index_directory_job(
+        &self,
+        path: &Path,
+        cancel: CancellationToken,
+    ) -> Result<Receiver<IndexProgress>> {
+        let (tx, rx) = channel();
+        let path = path.to_path_buf();
+        let matcher = self.matcher.clone();
+        let index = Arc::clone(&self.index);
+        let duplicates = Arc::clone(&self.duplicates);
+        let artifact_queue = self.artifact_queue.clone();
+        let semantic_index = self.semantic_index.clone();
+        let embedder = Arc::clone(&self.embedder);
+
+        std::thread::spawn(move || {
+            Self::run_index_job(
+                &path,
+                &matcher,
+                &index,
+                &duplicates,
+                &artifact_queue,
+                &semantic_index,
+                &embedder,
+                &cancel,
+                &tx,
+            );
+        });
 
-        Ok(FileEntry {
-            path: path.to_path_buf(),
-            file_name,
-            extension,
-            size_bytes: metadata.len(),
-            modified: metadata.modified().unwrap_or(SystemTime::now()),
-        })
+        Ok(rx)
     }
 
-    /// Check if a file should be skipped during indexing
-    fn should_skip(&self, path: &Path) -> bool {
-        let path_str = path.to_string_lossy().to_lowercase();
+    /// Walks `path` with a bounded `rayon` worker pool, indexing files
+    /// concurrently and reporting an `IndexProgress` snapshot after each
+    /// one. Workers stop picking up new files once `cancel` is set, and the
+    /// job's state (what's left to do, what's mid-upload, what's done) is
+    /// checkpointed periodically and on exit, so a later call resumes
+    /// instead of restarting.
+    fn run_index_job(
+        path: &Path,
+        matcher: &MatchList,
+        index: &Arc<Mutex<HashMap<PathBuf, FileEntry>>>,
+        duplicates: &Arc<Mutex<HashMap<String, Vec<PathBuf>>>>,
+        artifact_queue: &ArtifactQueue,
+        semantic_index: &VectorStore,
+        embedder: &Arc<dyn Embedder>,
+        cancel: &CancellationToken,
+        progress_tx: &Sender<IndexProgress>,
+    ) {
+        println!("[LIBRARIAN] Indexing directory: {}", path.display());
 
-        // Skip hidden files (starting with .)
-        if let Some(file_name) = path.file_name() {
-            if file_name.to_string_lossy().starts_with('.') {
-                return true;
-            }
+        let mut checkpoint = Checkpoint::load(path);
+        if checkpoint.pending.is_empty() && checkpoint.completed.is_empty() {
+            checkpoint.pending = WalkDir::new(path)
+                .follow_links(false)
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().is_file())
+                .map(|e| e.path().to_path_buf())
+                .filter(|p| !should_skip_with(p, matcher))
+                .collect();
+        } else {
+            println!(
+                "[LIBRARIAN] Resuming index of {}: {} completed, {} still pending",
+                path.display(),
+                checkpoint.completed.len(),
+                checkpoint.pending.len()
+            );
         }
 
-        // Skip common system/build directories
-        let skip_dirs = [
-            "node_modules",
-            "target",
-            ".git",
-            ".vscode",
-            "dist",
-            "build",
-            "__pycache__",
-            ".next",
-            ".cache",
-        ];
-
-        for skip_dir in &skip_dirs {
-            if path_str.contains(skip_dir) {
-                return true;
+        let entries: Vec<PathBuf> = checkpoint.pending.drain(..).collect();
+        let completed: Mutex<HashSet<PathBuf>> = Mutex::new(checkpoint.completed);
+        let in_flight: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+        let files_seen = AtomicU64::new(completed.lock().unwrap().len() as u64);
+        let files_indexed = AtomicU64::new(completed.lock().unwrap().len() as u64);
+        let bytes_indexed = AtomicU64::new(0);
+        let last_checkpoint = Mutex::new(Instant::now());
+
+        let save_checkpoint = |remaining: &[PathBuf]| {
+            let snapshot = Checkpoint {
+                pending: remaining
+                    .iter()
+                    .filter(|p| !completed.lock().unwrap().contains(*p))
+                    .filter(|p| !in_flight.lock().unwrap().contains(p))
+                    .cloned()
+                    .collect(),
+                completed: completed.lock().unwrap().clone(),
+                in_flight: in_flight.lock().unwrap().clone(),
+            };
+            if let Err(e) = snapshot.save(path) {
+                eprintln!("[LIBRARIAN] Failed to save index checkpoint: {}", e);
             }
-        }
+        };
 
-        // Skip very large files (> 100MB)
-        if let Ok(metadata) = std::fs::metadata(path) {
-            if metadata.len() > 100 * 1024 * 1024 {
-                return true;
+        let pool = match rayon::ThreadPoolBuilder::new()
+            .num_threads(INDEX_WORKER_THREADS)
+            .build()
+        {
+            Ok(pool) => pool,
+            Err(e) => {
+                eprintln!("[LIBRARIAN] Failed to build index worker pool: {}", e);
+                return;
             }
-        }
+        };
 
-        false
-    }
+        pool.install(|| {
+            entries.par_iter().for_each(|file_path| {
+                if cancel.is_cancelled() {
+                    return;
+                }
 
-    /// Send a file entry to the Go backend as an artifact
-    fn send_file_artifact(&self, file_entry: &FileEntry) -> Result<()> {
-        let artifact_type = match file_entry.extension.as_deref() {
-            Some("pdf") | Some("docx") | Some("txt") | Some("md") => "DOCUMENT",
-            Some("jpg") | Some("png") | Some("gif") | Some("bmp") => "IMAGE",
-            Some("mp3") | Some("wav") | Some("flac") => "AUDIO",
-            Some("mp4") | Some("avi") | Some("mkv") => "VIDEO",
-            Some("zip") | Some("rar") | Some("7z") => "ARCHIVE",
-            Some("exe") | Some("msi") | Some("app") => "EXECUTABLE",
-            Some("sav") | Some("dat") | Some("save") => "GAME_SAVE",
-            _ => "FILE",
-        };
+                in_flight.lock().unwrap().push(file_path.clone());
+                files_seen.fetch_add(1, Ordering::Relaxed);
+
+                match Self::create_file_entry_static(file_path) {
+                    Ok(file_entry) => {
+                        index
+                            .lock()
+                            .unwrap()
+                            .insert(file_path.clone(), file_entry.clone());
+
+                        let duplicate_of = register_duplicate(duplicates, &file_entry);
+                        if let Err(e) = Self::send_file_artifact_static(
+                            artifact_queue,
+                            semantic_index,
+                            embedder,
+                            &file_entry,
+                            &duplicate_of,
+                        ) {
+                            eprintln!("[LIBRARIAN] Failed to enqueue artifact: {}", e);
+                        }
 
-        // Create artifact content with full path for RAG
-        let content = format!(
-            "{} | {} | {}",
-            file_entry.path.display(),
-            file_entry.file_name,
-            artifact_type
-        );
-
-        let payload = serde_json::json!({
-            "type": artifact_type,
-            "content": content,
-            "metadata": {
-                "file_path": file_entry.path.display().to_string(),
-                "file_name": file_entry.file_name,
-                "extension": file_entry.extension,
-                "size_bytes": file_entry.size_bytes,
-            }
-        });
+                        files_indexed.fetch_add(1, Ordering::Relaxed);
+                        bytes_indexed.fetch_add(file_entry.size_bytes, Ordering::Relaxed);
+                        completed.lock().unwrap().insert(file_path.clone());
+                    }
+                    Err(e) => {
+                        eprintln!("[LIBRARIAN] Failed to index {}: {}", file_path.display(), e);
+                    }
+                }
 
-        // Send to Go backend
-        let client = reqwest::blocking::Client::new();
-        client
-            .post(&self.api_url)
-            .json(&payload)
-            .send()
-            .context("Failed to send artifact to Go backend")?;
+                in_flight.lock().unwrap().retain(|p| p != file_path);
+
+                let _ = progress_tx.send(IndexProgress {
+                    files_seen: files_seen.load(Ordering::Relaxed),
+                    files_indexed: files_indexed.load(Ordering::Relaxed),
+                    bytes_indexed: bytes_indexed.load(Ordering::Relaxed),
+                    current_path: Some(file_path.clone()),
+                });
+
+                let files_indexed_now = files_indexed.load(Ordering::Relaxed);
+                let due_by_count = files_indexed_now % CHECKPOINT_EVERY_FILES == 0;
+                let due_by_timer = {
+                    let mut last = last_checkpoint.lock().unwrap();
+                    if last.elapsed() >= CHECKPOINT_INTERVAL {
+                        *last = Instant::now();
+                        true
+                    } else {
+                        false
+                    }
+                };
+                if due_by_count || due_by_timer {
+                    save_checkpoint(&entries);
+                }
+            });
+        });
 
-        Ok(())
+        if cancel.is_cancelled() {
+            println!(
+                "[LIBRARIAN] Indexing of {} cancelled; checkpoint saved for resume",
+                path.display()
+            );
+            save_checkpoint(&entries);
+        } else {
+            println!(
+                "[LIBRARIAN] Indexed {} files from {}",
+                files_indexed.load(Ordering::Relaxed),
+                path.display()
+            );
+            if let Err(e) = Checkpoint::clear(path) {
+                eprintln!("[LIBRARIAN] Failed to clear index checkpoint: {}", e);
+            }
+        }
     }
 
-    /// Start watching directories for file system changes
-    pub fn start_watching(self) -> Result<()> {
+    /// Start watching directories for file system changes. Takes `&self`
+    /// rather than consuming it so a caller can keep another handle around
+    /// (e.g. to answer `SEARCH` queries against `semantic_index` while this
+    /// runs forever on its own thread).
+    pub fn start_watching(&self) -> Result<()> {
         let index = Arc::clone(&self.index);
-        let api_url = self.api_url.clone();
+        let duplicates = Arc::clone(&self.duplicates);
+        let artifact_queue = self.artifact_queue.clone();
+        let semantic_index = self.semantic_index.clone();
+        let embedder = Arc::clone(&self.embedder);
 
         // Create channel for file system events
         let (tx, rx): (Sender<Result<Event, notify::Error>>, Receiver<Result<Event, notify::Error>>) = channel();
@@ -226,94 +355,151 @@ impl Librarian {
 
         println!("[LIBRARIAN] File system watcher started");
 
-        // Process events
+        // Pending changes, keyed by path, waiting for DEBOUNCE_WINDOW of
+        // quiet before they're actually applied.
+        let mut pending: HashMap<PathBuf, (ChangeKind, Instant)> = HashMap::new();
+
+        // Process events: wait up to one debounce window at a time so a
+        // quiet path always gets flushed even if no further events arrive.
         loop {
-            match rx.recv() {
-                Ok(Ok(event)) => {
-                    Self::handle_fs_event(&index, &api_url, event);
-                }
-                Ok(Err(e)) => {
-                    eprintln!("[LIBRARIAN] Watch error: {}", e);
-                }
-                Err(e) => {
-                    eprintln!("[LIBRARIAN] Channel error: {}", e);
+            match rx.recv_timeout(DEBOUNCE_WINDOW) {
+                Ok(Ok(event)) => Self::fold_fs_event(&mut pending, event, &self.matcher),
+                Ok(Err(e)) => eprintln!("[LIBRARIAN] Watch error: {}", e),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    eprintln!("[LIBRARIAN] Watch channel disconnected");
                     break;
                 }
             }
+
+            Self::flush_quiet_changes(
+                &mut pending,
+                &index,
+                &duplicates,
+                &artifact_queue,
+                &semantic_index,
+                &embedder,
+            );
         }
 
         Ok(())
     }
 
-    /// Handle file system events
-    fn handle_fs_event(
-        index: &Arc<Mutex<HashMap<PathBuf, FileEntry>>>,
-        api_url: &str,
+    /// Folds a raw `notify` event into the pending-changes map, resetting
+    /// each affected path's quiet timer. `Create` followed by `Write` stays
+    /// a single pending re-index; a `Remove` overrides whatever was pending.
+    /// Uses the same `matcher` as the initial walk so both apply identical
+    /// skip semantics.
+    fn fold_fs_event(
+        pending: &mut HashMap<PathBuf, (ChangeKind, Instant)>,
         event: Event,
+        matcher: &MatchList,
     ) {
         use notify::EventKind;
 
+        let now = Instant::now();
         match event.kind {
-            EventKind::Create(_) | EventKind::Modify(_) => {
+            EventKind::Create(_) => {
                 for path in event.paths {
-                    if path.is_file() && !Self::should_skip_static(&path) {
-                        // Re-index the file
-                        if let Ok(file_entry) = Self::create_file_entry_static(&path) {
-                            let mut idx = index.lock().unwrap();
-                            idx.insert(path.clone(), file_entry.clone());
-                            drop(idx);
-
-                            println!("[LIBRARIAN] Indexed: {}", path.display());
-
-                            // Send to backend
-                            if let Err(e) = Self::send_file_artifact_static(api_url, &file_entry) {
-                                eprintln!("[LIBRARIAN] Failed to send artifact: {}", e);
-                            }
-                        }
+                    if !should_skip_with(&path, matcher) {
+                        pending.insert(path, (ChangeKind::Create, now));
                     }
                 }
             }
+            EventKind::Modify(_) => {
+                for path in event.paths {
+                    if should_skip_with(&path, matcher) {
+                        continue;
+                    }
+                    pending
+                        .entry(path)
+                        .and_modify(|(kind, seen_at)| {
+                            if *kind != ChangeKind::Remove {
+                                *kind = ChangeKind::Write;
+                            }
+                            *seen_at = now;
+                        })
+                        .or_insert((ChangeKind::Write, now));
+                }
+            }
             EventKind::Remove(_) => {
                 for path in event.paths {
-                    let mut idx = index.lock().unwrap();
-                    idx.remove(&path);
-                    println!("[LIBRARIAN] Removed from index: {}", path.display());
+                    pending.insert(path, (ChangeKind::Remove, now));
                 }
             }
             _ => {}
         }
     }
 
-    // Static versions of methods for use in event handler
-    fn should_skip_static(path: &Path) -> bool {
-        let path_str = path.to_string_lossy().to_lowercase();
-
-        if let Some(file_name) = path.file_name() {
-            if file_name.to_string_lossy().starts_with('.') {
-                return true;
-            }
-        }
-
-        let skip_dirs = [
-            "node_modules", "target", ".git", ".vscode", "dist",
-            "build", "__pycache__", ".next", ".cache",
-        ];
+    /// Applies every pending change that has been quiet for the full
+    /// `DEBOUNCE_WINDOW`, removing it from `pending`.
+    fn flush_quiet_changes(
+        pending: &mut HashMap<PathBuf, (ChangeKind, Instant)>,
+        index: &Arc<Mutex<HashMap<PathBuf, FileEntry>>>,
+        duplicates: &Arc<Mutex<HashMap<String, Vec<PathBuf>>>>,
+        artifact_queue: &ArtifactQueue,
+        semantic_index: &VectorStore,
+        embedder: &Arc<dyn Embedder>,
+    ) {
+        let now = Instant::now();
+        let ready: Vec<PathBuf> = pending
+            .iter()
+            .filter(|(_, (_, seen_at))| now.duration_since(*seen_at) >= DEBOUNCE_WINDOW)
+            .map(|(path, _)| path.clone())
+            .collect();
+
+        for path in ready {
+            let Some((kind, _)) = pending.remove(&path) else {
+                continue;
+            };
 
-        for skip_dir in &skip_dirs {
-            if path_str.contains(skip_dir) {
-                return true;
-            }
-        }
+            match kind {
+                ChangeKind::Remove => {
+                    index.lock().unwrap().remove(&path);
+                    semantic_index.remove_file(&path);
+                    println!("[LIBRARIAN] Removed from index: {}", path.display());
+                }
+                ChangeKind::Create | ChangeKind::Write => {
+                    if !path.is_file() {
+                        continue;
+                    }
+                    if let Ok(file_entry) = Self::create_file_entry_static(&path) {
+                        let previous_hash =
+                            index.lock().unwrap().get(&path).and_then(|e| e.content_hash.clone());
+                        let unchanged = kind == ChangeKind::Write
+                            && file_entry.content_hash.is_some()
+                            && previous_hash == file_entry.content_hash;
+
+                        index.lock().unwrap().insert(path.clone(), file_entry.clone());
+
+                        if unchanged {
+                            println!(
+                                "[LIBRARIAN] Content unchanged, skipping re-index: {}",
+                                path.display()
+                            );
+                            continue;
+                        }
 
-        if let Ok(metadata) = std::fs::metadata(path) {
-            if metadata.len() > 100 * 1024 * 1024 {
-                return true;
+                        println!("[LIBRARIAN] Indexed: {}", path.display());
+
+                        let duplicate_of = register_duplicate(duplicates, &file_entry);
+                        if let Err(e) = Self::send_file_artifact_static(
+                            artifact_queue,
+                            semantic_index,
+                            embedder,
+                            &file_entry,
+                            &duplicate_of,
+                        ) {
+                            eprintln!("[LIBRARIAN] Failed to enqueue artifact: {}", e);
+                        }
+                    }
+                }
             }
         }
-
-        false
     }
 
+    /// Builds a `FileEntry` from a path without borrowing `self`, so it can
+    /// run from the parallel index job and the file-watch event handler.
     fn create_file_entry_static(path: &Path) -> Result<FileEntry> {
         let metadata = std::fs::metadata(path)?;
 
@@ -334,42 +520,201 @@ impl Librarian {
             extension,
             size_bytes: metadata.len(),
             modified: metadata.modified().unwrap_or(SystemTime::now()),
+            content_hash: hash_file_best_effort(path),
         })
     }
 
-    fn send_file_artifact_static(api_url: &str, file_entry: &FileEntry) -> Result<()> {
-        let artifact_type = match file_entry.extension.as_deref() {
-            Some("pdf") | Some("docx") | Some("txt") | Some("md") => "DOCUMENT",
-            Some("jpg") | Some("png") | Some("gif") | Some("bmp") => "IMAGE",
-            Some("mp3") | Some("wav") | Some("flac") => "AUDIO",
-            Some("mp4") | Some("avi") | Some("mkv") => "VIDEO",
-            Some("zip") | Some("rar") | Some("7z") => "ARCHIVE",
-            Some("exe") | Some("msi") | Some("app") => "EXECUTABLE",
-            Some("sav") | Some("dat") | Some("save") => "GAME_SAVE",
-            _ => "FILE",
-        };
+    /// Enqueues `file_entry` onto the artifact queue. `DOCUMENT` files are
+    /// loaded, chunked, and enqueued as one artifact per chunk so the
+    /// backend has real text to embed; every other type keeps the
+    /// lightweight path-only artifact. `duplicate_of` lists any other
+    /// watched paths sharing this file's content hash. Returns once the
+    /// artifact(s) are queued, not once they've reached the backend.
+    fn send_file_artifact_static(
+        artifact_queue: &ArtifactQueue,
+        semantic_index: &VectorStore,
+        embedder: &Arc<dyn Embedder>,
+        file_entry: &FileEntry,
+        duplicate_of: &[PathBuf],
+    ) -> Result<()> {
+        let artifact_type = classify_artifact_type(file_entry.extension.as_deref());
+
+        if artifact_type == "DOCUMENT" {
+            match crate::document_loader::load_document(&file_entry.path) {
+                Ok(text) => {
+                    let vectors = crate::semantic_index::embed_file(embedder.as_ref(), &file_entry.path, &text);
+                    semantic_index.upsert_file(&file_entry.path, vectors);
+                    return send_document_chunks(artifact_queue, file_entry, &text, duplicate_of);
+                }
+                Err(e) => {
+                    eprintln!(
+                        "[LIBRARIAN] Failed to extract text from {}: {} (falling back to path-only artifact)",
+                        file_entry.path.display(),
+                        e
+                    );
+                }
+            }
+        }
+
+        send_path_only_artifact(artifact_queue, file_entry, artifact_type, duplicate_of)
+    }
+}
+
+/// Whether `path` should be skipped during indexing: excluded by `matcher`,
+/// or too large to be worth indexing at all. Shared by the initial walk and
+/// the event handler so both apply identical semantics.
+fn should_skip_with(path: &Path, matcher: &MatchList) -> bool {
+    if matcher.is_excluded(path) {
+        return true;
+    }
+
+    if let Ok(metadata) = std::fs::metadata(path) {
+        if metadata.len() > MAX_INDEXABLE_BYTES {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Hashes `path`'s content, logging and returning `None` on failure rather
+/// than aborting indexing over a hashing error.
+fn hash_file_best_effort(path: &Path) -> Option<String> {
+    match crate::content_hash::hash_file(path) {
+        Ok(hash) => Some(hash),
+        Err(e) => {
+            eprintln!("[LIBRARIAN] Failed to hash {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// Registers `file_entry`'s path under its content hash in the reverse
+/// duplicate index, returning every other path already known to share that
+/// hash. A no-op (returns an empty list) if hashing failed.
+fn register_duplicate(
+    duplicates: &Arc<Mutex<HashMap<String, Vec<PathBuf>>>>,
+    file_entry: &FileEntry,
+) -> Vec<PathBuf> {
+    let Some(hash) = &file_entry.content_hash else {
+        return Vec::new();
+    };
+
+    let mut duplicates = duplicates.lock().unwrap();
+    let paths = duplicates.entry(hash.clone()).or_default();
+    if !paths.contains(&file_entry.path) {
+        paths.push(file_entry.path.clone());
+    }
 
-        let content = format!(
-            "{} | {} | {}",
-            file_entry.path.display(),
-            file_entry.file_name,
-            artifact_type
-        );
+    paths
+        .iter()
+        .filter(|p| **p != file_entry.path)
+        .cloned()
+        .collect()
+}
+
+/// Classifies a file extension into the artifact type the Go backend
+/// expects.
+fn classify_artifact_type(extension: Option<&str>) -> &'static str {
+    match extension {
+        Some("pdf") | Some("docx") | Some("txt") | Some("md") => "DOCUMENT",
+        Some("jpg") | Some("png") | Some("gif") | Some("bmp") => "IMAGE",
+        Some("mp3") | Some("wav") | Some("flac") => "AUDIO",
+        Some("mp4") | Some("avi") | Some("mkv") => "VIDEO",
+        Some("zip") | Some("rar") | Some("7z") => "ARCHIVE",
+        Some("exe") | Some("msi") | Some("app") => "EXECUTABLE",
+        Some("sav") | Some("dat") | Some("save") => "GAME_SAVE",
+        _ => "FILE",
+    }
+}
+
+/// Adds a `duplicate_of` array of path strings to `payload`'s `metadata`
+/// object, but only when there actually are duplicates to report.
+fn tag_duplicates(payload: &mut serde_json::Value, duplicate_of: &[PathBuf]) {
+    if duplicate_of.is_empty() {
+        return;
+    }
+    payload["metadata"]["duplicate_of"] = serde_json::Value::from(
+        duplicate_of
+            .iter()
+            .map(|p| p.display().to_string())
+            .collect::<Vec<_>>(),
+    );
+}
+
+/// The lightweight, path-only artifact enqueued for every non-document type.
+fn send_path_only_artifact(
+    artifact_queue: &ArtifactQueue,
+    file_entry: &FileEntry,
+    artifact_type: &str,
+    duplicate_of: &[PathBuf],
+) -> Result<()> {
+    let content = format!(
+        "{} | {} | {}",
+        file_entry.path.display(),
+        file_entry.file_name,
+        artifact_type
+    );
+
+    let mut payload = serde_json::json!({
+        "type": artifact_type,
+        "content": content,
+        "metadata": {
+            "file_path": file_entry.path.display().to_string(),
+            "file_name": file_entry.file_name,
+            "extension": file_entry.extension,
+            "size_bytes": file_entry.size_bytes,
+            "content_hash": file_entry.content_hash,
+        }
+    });
+    tag_duplicates(&mut payload, duplicate_of);
 
-        let payload = serde_json::json!({
-            "type": artifact_type,
-            "content": content,
+    if matches!(artifact_type, "IMAGE" | "AUDIO" | "VIDEO") {
+        let media = crate::media_probe::probe_media(&file_entry.path, artifact_type);
+        if let Some(metadata) = payload["metadata"].as_object_mut() {
+            media.merge_into(metadata);
+        }
+    }
+
+    artifact_queue.send(payload)
+}
+
+/// Chunks `text` (~1000 chars with 200 overlap) and enqueues one artifact
+/// per chunk, each carrying `chunk_index`/`char_start`/`char_end` alongside
+/// the usual file fields.
+fn send_document_chunks(
+    artifact_queue: &ArtifactQueue,
+    file_entry: &FileEntry,
+    text: &str,
+    duplicate_of: &[PathBuf],
+) -> Result<()> {
+    let chunks = crate::document_loader::chunk_text(text, 1000, 200);
+
+    for chunk in &chunks {
+        let mut payload = serde_json::json!({
+            "type": "DOCUMENT",
+            "content": chunk.text,
             "metadata": {
                 "file_path": file_entry.path.display().to_string(),
                 "file_name": file_entry.file_name,
                 "extension": file_entry.extension,
                 "size_bytes": file_entry.size_bytes,
+                "content_hash": file_entry.content_hash,
+                "chunk_index": chunk.chunk_index,
+                "char_start": chunk.char_start,
+                "char_end": chunk.char_end,
             }
         });
-
-        let client = reqwest::blocking::Client::new();
-        client.post(api_url).json(&payload).send()?;
-
-        Ok(())
+        tag_duplicates(&mut payload, duplicate_of);
+
+        artifact_queue.send(payload).with_context(|| {
+            format!(
+                "Failed to enqueue chunk {} of {}",
+                chunk.chunk_index,
+                file_entry.path.display()
+            )
+        })?;
     }
+
+    Ok(())
 }
@@ -1,25 +1,123 @@
 // Author: Enkae (enkae.dev@pm.me)
 mod accessibility;
+mod artifact_queue;
+mod config;
+mod content_hash;
+mod document_loader;
 mod effector;
+mod hooks;
+mod index_job;
+mod ipc_session;
+mod keybind;
 mod librarian;
-
-use anyhow::Result;
-use accessibility::UIElement;
+mod match_list;
+mod media_probe;
+mod modifier_state;
+mod semantic_index;
+
+use anyhow::{Context, Result};
+use accessibility::{Capturer, PlatformCapturer};
+use clap::{Parser, Subcommand};
 use serde_json;
 use std::fs;
 use std::io::{self, BufRead, Write};
 use std::net::TcpStream;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 use std::thread;
 use std::time::Duration;
-use windows::Win32::System::Com::{CoCreateInstance, CoInitializeEx, COINIT_MULTITHREADED, CLSCTX_INPROC_SERVER};
-use windows::Win32::UI::Accessibility::{IUIAutomation, CUIAutomation};
 
-const API_BASE_URL: &str = "http://localhost:3000";
+/// How many chunks a `SEARCH` command returns, highest cosine similarity
+/// first.
+const SEARCH_TOP_K: usize = 5;
 
 static AUTH_TOKEN: OnceLock<Option<String>> = OnceLock::new();
 
+/// Ghost Sentinel: accessibility capture, action effector, and file
+/// librarian daemon. Global flags apply to whichever subcommand needs
+/// them; a mode that doesn't use a given flag (e.g. `capture` and
+/// `--watch`) simply ignores it.
+#[derive(Parser, Debug)]
+#[command(name = "ghost-sentinel", about = "Ghost Sentinel agent")]
+struct Cli {
+    #[command(subcommand)]
+    mode: Mode,
+
+    /// Base URL of the Ghost backend API.
+    #[arg(long, global = true, default_value = "http://localhost:3000")]
+    api_url: String,
+
+    /// `host:port` of the Go Kernel's focus-update listener.
+    #[arg(long, global = true, default_value = "127.0.0.1:5005")]
+    kernel_addr: String,
+
+    /// Path to the `ghost.token` auth token file. Falls back to the usual
+    /// candidate locations (cwd, `bin/`, next to the executable) if unset.
+    #[arg(long, global = true)]
+    token_path: Option<PathBuf>,
+
+    /// Max depth a SCAN/`scan_full_tree` call walks the UI tree.
+    #[arg(long, global = true, default_value_t = 3)]
+    scan_depth: u32,
+
+    /// Milliseconds between focus-capture polls.
+    #[arg(long, global = true, default_value_t = 500)]
+    capture_interval_ms: u64,
+
+    /// Directory to watch/index (repeatable). Overrides the built-in
+    /// default watch list (Documents/Desktop/Downloads/Pictures/AppData\Roaming)
+    /// when given at least once.
+    #[arg(long = "watch", global = true)]
+    watch_dirs: Vec<PathBuf>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Mode {
+    /// Accessibility tree capture of the focused element (one-shot).
+    Capture,
+    /// Action execution loop.
+    Effector,
+    /// File indexing and watching.
+    Librarian,
+    /// IPC daemon over a named-pipe session: capture + effector + scan + search.
+    Hybrid,
+    /// State-aware daemon: capture + effector + librarian, gated on ACTIVE/SHADOW/PAUSED.
+    Full,
+}
+
+/// Resolved runtime configuration, threaded through every mode instead of
+/// the hardcoded constants and literals they used to read directly.
+#[derive(Debug, Clone)]
+struct SentinelConfig {
+    api_url: String,
+    kernel_addr: String,
+    scan_depth: u32,
+    capture_interval_ms: u64,
+    watch_dirs: Vec<PathBuf>,
+}
+
+impl SentinelConfig {
+    fn from_cli(cli: &Cli) -> Self {
+        Self {
+            api_url: cli.api_url.clone(),
+            kernel_addr: cli.kernel_addr.clone(),
+            scan_depth: cli.scan_depth,
+            capture_interval_ms: cli.capture_interval_ms,
+            watch_dirs: cli.watch_dirs.clone(),
+        }
+    }
+
+    /// The directories the librarian indexes: the explicit `--watch` list if
+    /// any was given, otherwise the built-in platform defaults.
+    fn watch_dirs(&self) -> Vec<PathBuf> {
+        if self.watch_dirs.is_empty() {
+            default_watch_dirs()
+        } else {
+            self.watch_dirs.clone()
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 enum AppState {
     Active,
@@ -36,10 +134,18 @@ impl AppState {
             _ => None,
         }
     }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            AppState::Active => "ACTIVE",
+            AppState::Shadow => "SHADOW",
+            AppState::Paused => "PAUSED",
+        }
+    }
 }
 
-fn fetch_state() -> AppState {
-    match reqwest::blocking::get(format!("{}/api/state", API_BASE_URL)) {
+fn fetch_state(api_url: &str) -> AppState {
+    match reqwest::blocking::get(format!("{}/api/state", api_url)) {
         Ok(response) => {
             if let Ok(json) = response.json::<serde_json::Value>() {
                 if let Some(state_str) = json.get("state").and_then(|v| v.as_str()) {
@@ -62,66 +168,129 @@ fn main() {
 fn real_main() -> Result<()> {
     println!("[SENTINEL] Ghost Sentinel starting...");
 
-    // Preload auth token so we fail fast if it's missing
+    // Ask any running index job to checkpoint and unwind instead of being
+    // killed mid-write.
+    ctrlc::set_handler(|| {
+        println!("[SENTINEL] Caught Ctrl-C, checkpointing and shutting down...");
+        index_job::request_shutdown();
+        // Installing this handler replaces the OS's default terminate-on-
+        // SIGINT behavior, so we have to exit ourselves. Give any in-flight
+        // index job a moment to observe the flag and checkpoint first.
+        thread::sleep(Duration::from_millis(500));
+        std::process::exit(0);
+    })
+    .context("Failed to register Ctrl-C handler")?;
+
+    let cli = Cli::parse();
+    let config = SentinelConfig::from_cli(&cli);
+
+    // Preload auth token so a missing token is reported at startup. `set` is a no-op
+    // if something else already initialized it; nothing else does before
+    // this point, but the ordering still matters if that ever changes.
+    let _ = AUTH_TOKEN.set(load_auth_token(cli.token_path.as_deref()));
     if get_auth_token().is_none() {
         eprintln!("[SENTINEL] ⚠️ ghost.token not found. Focus updates will be disabled.");
     }
 
-    // Check if running in effector mode
-    let args: Vec<String> = std::env::args().collect();
-    let mode = args.get(1).map(|s| s.as_str());
-
-    match mode {
-        Some("--effector") => {
+    match cli.mode {
+        Mode::Effector => {
             println!("[SENTINEL] Running in EFFECTOR mode (action execution)");
-            effector::effector_loop(API_BASE_URL);
+            effector::effector_loop(&config.api_url);
             Ok(())
         }
-        Some("--librarian") => {
+        Mode::Librarian => {
             println!("[SENTINEL] Running in LIBRARIAN mode (file indexing)");
-            run_librarian_mode()
+            run_librarian_mode(&config, &index_job::CancellationToken::new())
         }
-        Some("--capture") | None => {
+        Mode::Capture => {
             println!("[SENTINEL] Running in CAPTURE mode (accessibility tree)");
             run_capture_mode()
         }
-        Some("--hybrid") => {
-            println!("[SENTINEL] Running in HYBRID mode (IPC Daemon: stdin/stdout)");
-            run_hybrid_daemon()
+        Mode::Hybrid => {
+            println!("[SENTINEL] Running in HYBRID mode (IPC Daemon: named-pipe session)");
+            run_hybrid_daemon(&config)
         }
-        Some("--full") => {
+        Mode::Full => {
             println!("[SENTINEL] Running in FULL mode (state-aware: capture + effector + librarian)");
-            run_full_mode()
-        }
-        Some(unknown) => {
-            eprintln!("[SENTINEL] Unknown mode: {}", unknown);
-            eprintln!("Usage: engram-sentinel [--capture|--effector|--librarian|--hybrid|--full]");
-            std::process::exit(1);
+            run_full_mode(&config)
         }
     }
 }
 
-/// Hybrid mode: Long-lived daemon that reads JSON commands from stdin
-fn run_hybrid_daemon() -> Result<()> {
+/// Hybrid mode: long-lived daemon that reads commands and writes results
+/// over a session of named pipes instead of stdin/stdout, so each kind of
+/// output (focus updates, scan trees, action results) is its own channel
+/// and a consumer never has to parse a `[SCAN_RESULT]`-style prefix. Logs
+/// stay on stderr throughout.
+fn run_hybrid_daemon(config: &SentinelConfig) -> Result<()> {
+    use std::sync::Arc;
+
     println!("[SENTINEL] Initializing IPC daemon...");
 
-    // Initialize COM for stdin thread (needed for SCAN commands)
-    // Note: Each thread that uses COM needs its own initialization
-    let stdin_handle = thread::spawn(|| {
-        // Initialize COM for this thread
-        if unsafe { CoInitializeEx(None, COINIT_MULTITHREADED) }.is_err() {
-            eprintln!("[SENTINEL] Failed to initialize COM in stdin thread");
-            return;
+    let session = ipc_session::IpcSession::create()?;
+    println!(
+        "[SENTINEL] Session pipes ready at {} (msg_in, focus_out, scan_out, result_out)",
+        session.dir().display()
+    );
+
+    let msg_in_path = session.msg_in();
+    let scan_out_path = session.scan_out();
+    let result_out_path = session.result_out();
+    let focus_out_path = session.focus_out();
+    let session_path = session.dir().display().to_string();
+
+    let hook_config = load_hook_config();
+    let macro_config = Arc::new(load_macro_config());
+
+    // Semantic index: do the initial indexing of the configured watch
+    // directories up front, then hand a shared handle to the watcher
+    // thread (keeps vectors fresh) and the msg_in thread (answers SEARCH).
+    let mut librarian = new_librarian(config);
+    let index_cancel = index_job::CancellationToken::new();
+    for dir in config.watch_dirs() {
+        if dir.exists() {
+            if let Err(e) = librarian.watch_directory(dir.clone(), &index_cancel) {
+                eprintln!("[SENTINEL] Failed to index {}: {}", dir.display(), e);
+            }
         }
+    }
+    let librarian = Arc::new(librarian);
+    {
+        let librarian = Arc::clone(&librarian);
+        thread::spawn(move || {
+            if let Err(e) = librarian.start_watching() {
+                eprintln!("[SENTINEL] Librarian watcher stopped: {}", e);
+            }
+        });
+    }
 
-        // Create UI Automation instance for SCAN commands
-        let scan_automation: Option<IUIAutomation> = unsafe {
-            CoCreateInstance(&CUIAutomation, None, CLSCTX_INPROC_SERVER).ok()
+    // Each thread that captures needs its own `Capturer` (on Windows, UI
+    // Automation objects are apartment-threaded and can't cross threads).
+    let search_librarian = Arc::clone(&librarian);
+    let scan_depth = config.scan_depth;
+    let macro_config_stdin = Arc::clone(&macro_config);
+    let stdin_handle = thread::spawn(move || {
+        let macro_config = macro_config_stdin;
+
+        let scan_capturer = match PlatformCapturer::new() {
+            Ok(capturer) => Some(capturer),
+            Err(e) => {
+                eprintln!("[SENTINEL] Failed to initialize capturer in msg_in thread: {}", e);
+                None
+            }
         };
 
-        println!("[SENTINEL] Stdin listener active. Awaiting JSON commands...");
-        let stdin = io::stdin();
-        let reader = stdin.lock();
+        println!("[SENTINEL] Opening msg_in; awaiting a reader on scan_out/result_out...");
+        let reader = match ipc_session::open_reader(&msg_in_path) {
+            Ok(file) => io::BufReader::new(file),
+            Err(e) => {
+                eprintln!("[SENTINEL] Failed to open msg_in: {}", e);
+                return;
+            }
+        };
+        let mut scan_out = ipc_session::open_writer(&scan_out_path).ok();
+        let mut result_out = ipc_session::open_writer(&result_out_path).ok();
+        println!("[SENTINEL] msg_in listener active. Awaiting commands...");
 
         for line in reader.lines() {
             match line {
@@ -133,63 +302,124 @@ fn run_hybrid_daemon() -> Result<()> {
 
                     // Check for SCAN command (Sovereign Sight)
                     if input_str == "SCAN" {
-                        if let Some(ref automation) = scan_automation {
-                            match scan_full_tree(automation, 3) {
-                                Ok(json_output) => {
-                                    println!("[SCAN_RESULT]{}", json_output);
-                                    if let Err(e) = io::stdout().flush() {
-                                        eprintln!("[SENTINEL] Stdout flush error: {}", e);
-                                    }
-                                }
-                                Err(e) => {
-                                    eprintln!("[SENTINEL] Scan error: {}", e);
-                                    println!("[SCAN_RESULT]{{\"error\": \"{}\"}}", e);
-                                    let _ = io::stdout().flush();
-                                }
+                        let json_output = match &scan_capturer {
+                            Some(capturer) => scan_full_tree(capturer, scan_depth)
+                                .unwrap_or_else(|e| format!("{{\"error\": \"{}\"}}", e)),
+                            None => "{\"error\": \"capturer not initialized\"}".to_string(),
+                        };
+                        write_channel_line(&mut scan_out, &scan_out_path, &json_output);
+                        continue;
+                    }
+
+                    // SEARCH <query>: semantic lookup over the Librarian's indexed
+                    // files. A query, not a mutating action, so its results go out
+                    // on scan_out alongside SCAN rather than on result_out.
+                    if let Some(query) = input_str.strip_prefix("SEARCH ") {
+                        let json_output = match search_librarian.search(query.trim(), SEARCH_TOP_K) {
+                            Ok(results) => {
+                                let hits: Vec<serde_json::Value> = results
+                                    .into_iter()
+                                    .map(|(score, entry)| {
+                                        serde_json::json!({
+                                            "path": entry.path,
+                                            "score": score,
+                                            "snippet": entry.snippet,
+                                        })
+                                    })
+                                    .collect();
+                                serde_json::json!({ "results": hits }).to_string()
                             }
-                        } else {
-                            eprintln!("[SENTINEL] SCAN unavailable: UI Automation not initialized");
-                            println!("[SCAN_RESULT]{{\"error\": \"UI Automation not initialized\"}}");
-                            let _ = io::stdout().flush();
-                        }
+                            Err(e) => format!("{{\"error\": \"{}\"}}", e),
+                        };
+                        write_channel_line(&mut scan_out, &scan_out_path, &json_output);
                         continue;
                     }
 
+                    // Element-targeted actions (CLICK_ELEMENT/FOCUS_ELEMENT) need a
+                    // freshly captured tree, so they're handled before the generic
+                    // JSON dispatch below.
+                    if let Some(command) = serde_json::from_str::<serde_json::Value>(input_str).ok() {
+                        let action = command.get("action").and_then(|v| v.as_str()).map(str::to_uppercase);
+                        if matches!(action.as_deref(), Some("CLICK_ELEMENT") | Some("FOCUS_ELEMENT")) {
+                            let ack = match &scan_capturer {
+                                Some(capturer) => handle_element_action(
+                                    capturer,
+                                    action.as_deref().unwrap(),
+                                    &command,
+                                    macro_config.as_ref().as_ref(),
+                                ),
+                                None => Err(anyhow::anyhow!("capturer not initialized")),
+                            };
+                            write_channel_line(&mut result_out, &result_out_path, &result_ack_json(&ack));
+                            if let Err(e) = ack {
+                                eprintln!("[SENTINEL] Element action error: {}", e);
+                            }
+                            continue;
+                        }
+                    }
+
                     // Execute the action (JSON command)
-                    if let Err(e) = effector::execute_action_json(input_str) {
+                    let result = effector::execute_action_json_with_config(
+                        input_str,
+                        macro_config.as_ref().as_ref(),
+                    );
+                    write_channel_line(&mut result_out, &result_out_path, &result_ack_json(&result));
+                    if let Err(e) = result {
                         eprintln!("[SENTINEL] Action execution error: {}", e);
                     }
                 }
                 Err(e) => {
-                    eprintln!("[SENTINEL] Stdin read error: {}", e);
+                    eprintln!("[SENTINEL] msg_in read error: {}", e);
                     break;
                 }
             }
         }
     });
 
-    // Initialize COM for the capture thread
-    unsafe { CoInitializeEx(None, COINIT_MULTITHREADED) }.ok()?;
-
-    let automation: IUIAutomation = unsafe {
-        CoCreateInstance(&CUIAutomation, None, CLSCTX_INPROC_SERVER)?
-    };
+    let capturer = PlatformCapturer::new()?;
+    let mut focus_out = ipc_session::open_writer(&focus_out_path)?;
+    let mut last_focus_name: Option<String> = None;
 
     // Main thread: Periodic capture loop
     loop {
-        thread::sleep(Duration::from_millis(500));
+        thread::sleep(Duration::from_millis(config.capture_interval_ms));
 
         // Capture focused element
-        match capture_focused_element(&automation) {
+        match capturer.focused_element() {
             Ok(ui_element) => {
                 // Send focus update to Go Kernel for safety verification
-                notify_kernel_focus(&ui_element.name);
+                notify_kernel_focus(&ui_element.name, &config.kernel_addr);
 
-                // Output to Python via stdout
+                // Publish to focus_out instead of stdout
                 if let Ok(json_output) = serde_json::to_string(&ui_element) {
-                    println!("{}", json_output);
-                    if let Err(e) = io::stdout().flush() {
-                        eprintln!("[SENTINEL] Stdout flush error: {}", e);
+                    if let Err(e) = writeln!(focus_out, "{}", json_output) {
+                        eprintln!("[SENTINEL] focus_out write error: {}", e);
+                    }
+                    let _ = focus_out.flush();
+                }
+
+                // Only fire on_focus hooks when the focused window actually
+                // changed, not on every capture tick. The hybrid daemon has
+                // no AppState gate of its own (it already executes effector
+                // actions ungated above), so hook-proposed actions run
+                // unconditionally too — pass AppState::Active to say so.
+                if last_focus_name.as_deref() != Some(ui_element.name.as_str()) {
+                    last_focus_name = Some(ui_element.name.clone());
+
+                    if let Some(hook_cfg) = hook_config.as_ref() {
+                        let context = hooks::HookContext {
+                            focus_name: Some(ui_element.name.clone()),
+                            control_type: Some(ui_element.control_type.clone()),
+                            bounding_rect: Some(ui_element.bounding_rectangle.clone()),
+                            session_path: Some(session_path.clone()),
+                            ..Default::default()
+                        };
+                        dispatch_hook_actions(
+                            &hook_cfg.on_focus,
+                            &context,
+                            &AppState::Active,
+                            macro_config.as_ref().as_ref(),
+                        );
                     }
                 }
             }
@@ -200,8 +430,31 @@ fn run_hybrid_daemon() -> Result<()> {
     }
 }
 
+/// Serializes an action result to the small ack/error shape `result_out`
+/// consumers expect: `{"ok": true}` or `{"ok": false, "error": "..."}`.
+fn result_ack_json(result: &Result<()>) -> String {
+    match result {
+        Ok(()) => "{\"ok\": true}".to_string(),
+        Err(e) => serde_json::json!({ "ok": false, "error": e.to_string() }).to_string(),
+    }
+}
+
+/// Writes one line to an already-opened output channel, logging (but not
+/// panicking) if the channel isn't open or the write fails — a missing
+/// reader on that pipe shouldn't take down the daemon.
+fn write_channel_line(out: &mut Option<fs::File>, path: &PathBuf, line: &str) {
+    let Some(file) = out else {
+        eprintln!("[SENTINEL] {} is not open; dropping a line", path.display());
+        return;
+    };
+    if let Err(e) = writeln!(file, "{}", line) {
+        eprintln!("[SENTINEL] Write error on {}: {}", path.display(), e);
+    }
+    let _ = file.flush();
+}
+
 /// Helper function to send focus update to the Go Kernel
-fn notify_kernel_focus(window_name: &str) {
+fn notify_kernel_focus(window_name: &str, kernel_addr: &str) {
     // Attempt to connect to the Kernel and send focus update
     // If kernel is unavailable, silently ignore (graceful degradation)
     let auth_token = match get_auth_token() {
@@ -209,7 +462,7 @@ fn notify_kernel_focus(window_name: &str) {
         None => return,
     };
 
-    if let Ok(mut stream) = TcpStream::connect("127.0.0.1:5005") {
+    if let Ok(mut stream) = TcpStream::connect(kernel_addr) {
         let auth_payload = serde_json::json!({ "auth_token": auth_token });
         if let Ok(auth_str) = serde_json::to_string(&auth_payload) {
             let _ = stream.write_all(format!("{}\n", auth_str).as_bytes());
@@ -231,59 +484,74 @@ fn notify_kernel_focus(window_name: &str) {
 
 /// Scan the entire UI tree from root (Sovereign Sight)
 /// Returns JSON string of the full UI tree up to max_depth
-fn scan_full_tree(automation: &IUIAutomation, max_depth: u32) -> Result<String> {
-    // Get the root element (Desktop)
-    let root_element = unsafe { automation.GetRootElement()? };
-
-    // Walk the tree using the accessibility module
-    let ui_tree = accessibility::walk_tree(&root_element, 0, max_depth)?;
-
-    // Serialize to JSON
+fn scan_full_tree(capturer: &dyn Capturer, max_depth: u32) -> Result<String> {
+    let ui_tree = capturer.walk_tree(max_depth)?;
     let json_output = serde_json::to_string(&ui_tree)?;
     Ok(json_output)
 }
 
-/// Helper function to capture focused UI element
-fn capture_focused_element(automation: &IUIAutomation) -> Result<UIElement> {
-    let element = unsafe { automation.GetFocusedElement()? };
-
-    let name = unsafe {
-        element.CurrentName()
-            .map(|s| s.to_string())
-            .unwrap_or_else(|_| String::from("Unknown"))
-    };
-
-    let control_type = unsafe {
-        element.CurrentLocalizedControlType()
-            .map(|s| s.to_string())
-            .unwrap_or_else(|_| String::from("Unknown"))
-    };
-
-    let bounding_rectangle = unsafe {
-        element.CurrentBoundingRectangle()
-            .map(|rect| format!(
-                "left={},top={},right={},bottom={}",
-                rect.left, rect.top, rect.right, rect.bottom
-            ))
-            .unwrap_or_else(|_| String::from("Unknown"))
-    };
+/// Resolves a `CLICK_ELEMENT`/`FOCUS_ELEMENT` command against a freshly
+/// captured UI tree and dispatches the resulting coordinates through the
+/// normal click path (`FOCUS_ELEMENT` focuses by clicking). Payload shape:
+/// `{ "name": "...", "control_type": "..." }` (either may be omitted).
+fn handle_element_action(
+    capturer: &dyn Capturer,
+    action: &str,
+    command: &serde_json::Value,
+    macro_config: Option<&config::Config>,
+) -> Result<()> {
+    let payload = command
+        .get("payload")
+        .ok_or_else(|| anyhow::anyhow!("Missing 'payload' field"))?;
+    let name = payload.get("name").and_then(|v| v.as_str());
+    let control_type = payload.get("control_type").and_then(|v| v.as_str());
+
+    let tree = capturer.walk_tree(5)?;
+
+    let (x, y) = accessibility::resolve_element_center(&tree, name, control_type)?;
+
+    match action {
+        "CLICK_ELEMENT" => {
+            println!("[SENTINEL] Resolved element to ({}, {}), dispatching click", x, y);
+            let click_json = serde_json::json!({
+                "action": "CLICK",
+                "payload": { "x": x, "y": y }
+            })
+            .to_string();
+            effector::execute_action_json_with_config(&click_json, macro_config)?;
+        }
+        "FOCUS_ELEMENT" => {
+            // No native accessibility SetFocus API is reachable from a plain
+            // coordinate pair, so the only focus mechanism the effector
+            // exposes is a click; dispatch one rather than leaving
+            // FOCUS_ELEMENT a no-op.
+            println!("[SENTINEL] Resolved focus target to ({}, {}), dispatching click-to-focus", x, y);
+            let click_json = serde_json::json!({
+                "action": "CLICK",
+                "payload": { "x": x, "y": y }
+            })
+            .to_string();
+            effector::execute_action_json_with_config(&click_json, macro_config)?;
+        }
+        other => anyhow::bail!("Unsupported element action: {}", other),
+    }
 
-    Ok(UIElement {
-        name,
-        control_type,
-        bounding_rectangle,
-        children: Vec::new(),
-    })
+    Ok(())
 }
 
 fn get_auth_token() -> Option<&'static str> {
     AUTH_TOKEN
-        .get_or_init(load_auth_token)
+        .get_or_init(|| load_auth_token(None))
         .as_deref()
 }
 
-fn load_auth_token() -> Option<String> {
+/// Loads the auth token, trying `override_path` first (from `--token-path`)
+/// before falling back to the usual candidate locations.
+fn load_auth_token(override_path: Option<&Path>) -> Option<String> {
     let mut candidates = Vec::new();
+    if let Some(path) = override_path {
+        candidates.push(path.to_path_buf());
+    }
     candidates.push(PathBuf::from("ghost.token"));
     candidates.push(PathBuf::from("bin").join("ghost.token"));
 
@@ -312,14 +580,96 @@ fn load_auth_token() -> Option<String> {
     None
 }
 
-fn run_capture_mode() -> Result<()> {
-    unsafe { CoInitializeEx(None, COINIT_MULTITHREADED) }.ok()?;
+/// Loads the operator's macro config (`ghost_macros.ron` or `.json5`) from
+/// the working directory, if present. Missing configs are not an error since
+/// macros are optional; a present-but-invalid config is logged and skipped.
+fn load_macro_config() -> Option<config::Config> {
+    for candidate in ["ghost_macros.ron", "ghost_macros.json5"] {
+        let path = PathBuf::from(candidate);
+        if path.exists() {
+            return match config::Config::load(&path) {
+                Ok(cfg) => {
+                    println!(
+                        "[SENTINEL] Loaded {} macro(s) from {}",
+                        cfg.macros.len(),
+                        path.display()
+                    );
+                    Some(cfg)
+                }
+                Err(e) => {
+                    eprintln!("[SENTINEL] Failed to load macro config {}: {}", path.display(), e);
+                    None
+                }
+            };
+        }
+    }
+    None
+}
 
-    let automation: IUIAutomation = unsafe {
-        CoCreateInstance(&CUIAutomation, None, CLSCTX_INPROC_SERVER)?
-    };
+/// Loads the operator's hook config (`ghost_hooks.ron`/`.json5`) from the
+/// same candidate locations `load_auth_token` checks, so hooks can ship
+/// alongside the token file. Missing is not an error; hooks are optional.
+fn load_hook_config() -> Option<hooks::HookConfig> {
+    let mut candidates = Vec::new();
+    for name in ["ghost_hooks.ron", "ghost_hooks.json5"] {
+        candidates.push(PathBuf::from(name));
+        candidates.push(PathBuf::from("bin").join(name));
+        if let Ok(exe_path) = std::env::current_exe() {
+            if let Some(bin_dir) = exe_path.parent() {
+                candidates.push(bin_dir.join(name));
+                if let Some(root_dir) = bin_dir.parent() {
+                    candidates.push(root_dir.join(name));
+                }
+            }
+        }
+    }
+
+    for path in candidates {
+        if path.exists() {
+            return match hooks::HookConfig::load(&path) {
+                Ok(config) => {
+                    println!("[SENTINEL] Loaded hook config from {}", path.display());
+                    Some(config)
+                }
+                Err(e) => {
+                    eprintln!("[SENTINEL] Failed to load hook config {}: {}", path.display(), e);
+                    None
+                }
+            };
+        }
+    }
+    None
+}
+
+/// Runs `commands` with `context`, then feeds any action JSON they propose
+/// through the effector — but only while `gate_state` is `Active`; in
+/// `Shadow`/`Paused` a proposed action is logged and discarded instead of
+/// executed, mirroring the state gate `run_full_mode` already applies to
+/// its own effector loop.
+fn dispatch_hook_actions(
+    commands: &[String],
+    context: &hooks::HookContext,
+    gate_state: &AppState,
+    macro_config: Option<&config::Config>,
+) {
+    for action_json in hooks::run_hooks(commands, context) {
+        if *gate_state != AppState::Active {
+            println!(
+                "[HOOKS] Discarding proposed action in {:?} state: {}",
+                gate_state, action_json
+            );
+            continue;
+        }
+        if let Err(e) = effector::execute_action_json_with_config(&action_json, macro_config) {
+            eprintln!("[HOOKS] Hook action failed: {}", e);
+        }
+    }
+}
+
+fn run_capture_mode() -> Result<()> {
+    let capturer = PlatformCapturer::new()?;
 
-    let ui_element = capture_focused_element(&automation)?;
+    let ui_element = capturer.focused_element()?;
     let json_output = serde_json::to_string(&ui_element)?;
     println!("{}", json_output);
     io::stdout().flush()?;
@@ -327,29 +677,36 @@ fn run_capture_mode() -> Result<()> {
     Ok(())
 }
 
-fn run_librarian_mode() -> Result<()> {
-    use librarian::Librarian;
-    use std::env;
-    use std::path::PathBuf;
+/// Common user locations (Windows) the librarian indexes by default, when
+/// no explicit watch list is configured.
+fn default_watch_dirs() -> Vec<PathBuf> {
+    let home_dir =
+        std::env::var("USERPROFILE").unwrap_or_else(|_| "C:\\Users\\Default".to_string());
 
-    let artifact_url = format!("{}/api/artifacts", API_BASE_URL);
-    let mut librarian = Librarian::new(artifact_url);
-
-    // Default directories to watch (common user locations on Windows)
-    let home_dir = env::var("USERPROFILE").unwrap_or_else(|_| "C:\\Users\\Default".to_string());
-
-    let default_dirs = vec![
+    vec![
         PathBuf::from(format!("{}\\Documents", home_dir)),
         PathBuf::from(format!("{}\\Desktop", home_dir)),
         PathBuf::from(format!("{}\\Downloads", home_dir)),
         PathBuf::from(format!("{}\\Pictures", home_dir)),
         PathBuf::from(format!("{}\\AppData\\Roaming", home_dir)), // Game saves often here
-    ];
+    ]
+}
 
-    // Add each directory that exists
-    for dir in default_dirs {
+fn new_librarian(config: &SentinelConfig) -> librarian::Librarian {
+    librarian::Librarian::new(
+        format!("{}/api/artifacts", config.api_url),
+        format!("{}/api/embeddings", config.api_url),
+        match_list::MatchList::defaults(),
+    )
+}
+
+fn run_librarian_mode(config: &SentinelConfig, cancel: &index_job::CancellationToken) -> Result<()> {
+    let mut librarian = new_librarian(config);
+
+    // Add each configured directory that exists
+    for dir in config.watch_dirs() {
         if dir.exists() {
-            librarian.watch_directory(dir)?;
+            librarian.watch_directory(dir, cancel)?;
         } else {
             println!("[LIBRARIAN] Skipping non-existent directory: {}", dir.display());
         }
@@ -363,21 +720,27 @@ fn run_librarian_mode() -> Result<()> {
     Ok(())
 }
 
-fn run_full_mode() -> Result<()> {
+fn run_full_mode(settings: &SentinelConfig) -> Result<()> {
     use std::sync::{Arc, Mutex};
 
-    let current_state = Arc::new(Mutex::new(fetch_state()));
+    let current_state = Arc::new(Mutex::new(fetch_state(&settings.api_url)));
     let state_clone_effector = Arc::clone(&current_state);
     let state_clone_librarian = Arc::clone(&current_state);
 
+    let hook_config = Arc::new(load_hook_config());
+    let macro_config = Arc::new(load_macro_config());
+
     println!("[SENTINEL] Initial state: {:?}", *current_state.lock().unwrap());
 
     // Spawn state poller thread
     let state_poller = Arc::clone(&current_state);
+    let hook_config_poller = Arc::clone(&hook_config);
+    let macro_config_poller = Arc::clone(&macro_config);
+    let api_url_poller = settings.api_url.clone();
     thread::spawn(move || {
         loop {
             thread::sleep(Duration::from_secs(1));
-            let new_state = fetch_state();
+            let new_state = fetch_state(&api_url_poller);
             let mut current = state_poller.lock().unwrap();
             if *current != new_state {
                 let emoji = match new_state {
@@ -386,19 +749,34 @@ fn run_full_mode() -> Result<()> {
                     AppState::Paused => "🔴",
                 };
                 println!("[STATE] {} Switched to: {:?}", emoji, new_state);
-                *current = new_state;
+                *current = new_state.clone();
+                drop(current);
+
+                if let Some(config) = hook_config_poller.as_ref() {
+                    let context = hooks::HookContext {
+                        state: Some(new_state.as_str().to_string()),
+                        ..Default::default()
+                    };
+                    dispatch_hook_actions(
+                        &config.on_state_change,
+                        &context,
+                        &new_state,
+                        macro_config_poller.as_ref().as_ref(),
+                    );
+                }
             }
         }
     });
 
     // Spawn effector thread (respects state)
+    let api_url_effector = settings.api_url.clone();
     thread::spawn(move || {
         loop {
             let state = state_clone_effector.lock().unwrap().clone();
             match state {
                 AppState::Active => {
                     // Only execute actions when ACTIVE
-                    effector::effector_loop(API_BASE_URL);
+                    effector::effector_loop(&api_url_effector);
                 }
                 AppState::Shadow | AppState::Paused => {
                     // In SHADOW or PAUSED, do not execute actions
@@ -409,15 +787,37 @@ fn run_full_mode() -> Result<()> {
     });
 
     // Spawn librarian thread (respects state)
+    let settings_librarian = settings.clone();
     thread::spawn(move || {
         loop {
             let state = state_clone_librarian.lock().unwrap().clone();
             match state {
                 AppState::Active | AppState::Shadow => {
-                    // Index files in ACTIVE or SHADOW mode
-                    if let Err(e) = run_librarian_mode() {
+                    // Index files in ACTIVE or SHADOW mode. A fresh token
+                    // each cycle so a resume after PAUSED isn't born
+                    // pre-cancelled; a side thread watches for the state
+                    // flipping to PAUSED mid-index and cancels it, which
+                    // makes the index job checkpoint instead of being cut
+                    // off mid-write.
+                    let cancel = index_job::CancellationToken::new();
+                    let cancel_watcher = cancel.clone();
+                    let state_watcher = Arc::clone(&state_clone_librarian);
+                    let watcher = thread::spawn(move || loop {
+                        thread::sleep(Duration::from_millis(200));
+                        if cancel_watcher.is_cancelled() {
+                            return;
+                        }
+                        if *state_watcher.lock().unwrap() == AppState::Paused {
+                            cancel_watcher.cancel();
+                            return;
+                        }
+                    });
+
+                    if let Err(e) = run_librarian_mode(&settings_librarian, &cancel) {
                         eprintln!("[LIBRARIAN] Error: {}", e);
                     }
+                    cancel.cancel();
+                    let _ = watcher.join();
                 }
                 AppState::Paused => {
                     // In PAUSED, do nothing
@@ -428,26 +828,46 @@ fn run_full_mode() -> Result<()> {
     });
 
     // Main thread: Run capture (respects state)
-    unsafe { CoInitializeEx(None, COINIT_MULTITHREADED) }.ok()?;
-    let automation: IUIAutomation = unsafe {
-        CoCreateInstance(&CUIAutomation, None, CLSCTX_INPROC_SERVER)?
-    };
+    let capturer = PlatformCapturer::new()?;
+    let mut last_focus_name: Option<String> = None;
 
     loop {
         let state = current_state.lock().unwrap().clone();
         match state {
             AppState::Active | AppState::Shadow => {
                 // Capture screen in ACTIVE or SHADOW mode
-                match capture_focused_element(&automation) {
+                match capturer.focused_element() {
                     Ok(ui_element) => {
                         // Send focus update to Go Kernel
-                        notify_kernel_focus(&ui_element.name);
+                        notify_kernel_focus(&ui_element.name, &settings.kernel_addr);
+
+                        // Only fire on_focus hooks when the focused window
+                        // actually changed, not on every capture poll.
+                        if last_focus_name.as_deref() != Some(ui_element.name.as_str()) {
+                            last_focus_name = Some(ui_element.name.clone());
+
+                            if let Some(config) = hook_config.as_ref() {
+                                let context = hooks::HookContext {
+                                    focus_name: Some(ui_element.name.clone()),
+                                    control_type: Some(ui_element.control_type.clone()),
+                                    bounding_rect: Some(ui_element.bounding_rectangle.clone()),
+                                    state: Some(state.as_str().to_string()),
+                                    session_path: None,
+                                };
+                                dispatch_hook_actions(
+                                    &config.on_focus,
+                                    &context,
+                                    &state,
+                                    macro_config.as_ref().as_ref(),
+                                );
+                            }
+                        }
                     }
                     Err(e) => {
                         eprintln!("[SENTINEL] Capture error: {}", e);
                     }
                 }
-                thread::sleep(Duration::from_millis(100));
+                thread::sleep(Duration::from_millis(settings.capture_interval_ms));
             }
             AppState::Paused => {
                 // In PAUSED, sleep and do nothing
@@ -0,0 +1,170 @@
+// Author: Enkae (enkae.dev@pm.me)
+//! Gitignore-style include/exclude path matching. Replaces the fixed
+//! `skip_dirs` array the Librarian used to hardcode with an ordered list of
+//! glob patterns a user can tune: exclude `target/` everywhere except one
+//! project, whitelist `*.pdf` while excluding everything else, and so on.
+//!
+//! Patterns use the `glob` crate's syntax. By default `*` and `**` both
+//! match across path separators, so `**/node_modules/**` and
+//! `*node_modules*` behave the same — pick whichever reads clearer.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// Whether a matching pattern includes (un-ignores) or excludes a path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchType {
+    Include,
+    Exclude,
+}
+
+#[derive(Debug, Clone)]
+struct MatchRule {
+    pattern: glob::Pattern,
+    match_type: MatchType,
+}
+
+/// An ordered list of include/exclude glob patterns, evaluated last-match-
+/// wins like a `.gitignore`: later entries override earlier ones, and a
+/// path matched by no rule is not excluded.
+#[derive(Debug, Clone, Default)]
+pub struct MatchList {
+    rules: Vec<MatchRule>,
+}
+
+impl MatchList {
+    pub fn new() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Appends one compiled pattern to the end of the list.
+    pub fn push(&mut self, pattern: &str, match_type: MatchType) -> Result<()> {
+        let pattern = glob::Pattern::new(pattern)
+            .with_context(|| format!("Invalid match pattern: {}", pattern))?;
+        self.rules.push(MatchRule { pattern, match_type });
+        Ok(())
+    }
+
+    /// This repo's longstanding default exclusions, kept so existing
+    /// installs behave the same until a user opts into their own config or
+    /// `.ghostignore`.
+    pub fn defaults() -> Self {
+        let mut list = Self::new();
+        for dir in [
+            "node_modules",
+            "target",
+            ".git",
+            ".vscode",
+            "dist",
+            "build",
+            "__pycache__",
+            ".next",
+            ".cache",
+        ] {
+            list.push(&format!("**/{}/**", dir), MatchType::Exclude)
+                .expect("built-in default pattern must compile");
+        }
+        list.push("**/.*", MatchType::Exclude)
+            .expect("built-in default pattern must compile");
+        list
+    }
+
+    /// Parses one pattern per non-empty, non-comment line, `.gitignore`
+    /// style: a leading `!` marks an `Include` (un-ignore) rule, everything
+    /// else is an `Exclude`.
+    pub fn parse_ignore_file(contents: &str) -> Result<Self> {
+        let mut list = Self::new();
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match line.strip_prefix('!') {
+                Some(pattern) => list.push(pattern, MatchType::Include)?,
+                None => list.push(line, MatchType::Exclude)?,
+            }
+        }
+        Ok(list)
+    }
+
+    /// Returns a copy of this list with `root`'s `.ghostignore` (if any)
+    /// appended, so the file's rules can override the base config without
+    /// mutating it.
+    pub fn extended_with_ignore_file(&self, root: &Path) -> Self {
+        let ignore_path = root.join(".ghostignore");
+        let Ok(contents) = std::fs::read_to_string(&ignore_path) else {
+            return self.clone();
+        };
+
+        match Self::parse_ignore_file(&contents) {
+            Ok(file_rules) => {
+                let mut combined = self.clone();
+                combined.rules.extend(file_rules.rules);
+                combined
+            }
+            Err(e) => {
+                eprintln!("[LIBRARIAN] Invalid {}: {}", ignore_path.display(), e);
+                self.clone()
+            }
+        }
+    }
+
+    /// Whether `path` should be excluded: the last matching rule wins, and a
+    /// path matched by nothing is included.
+    pub fn is_excluded(&self, path: &Path) -> bool {
+        let path_str = path.to_string_lossy().replace('\\', "/");
+        let mut excluded = false;
+        for rule in &self.rules {
+            if rule.pattern.matches(&path_str) {
+                excluded = rule.match_type == MatchType::Exclude;
+            }
+        }
+        excluded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_exclude_known_build_dirs() {
+        let list = MatchList::defaults();
+        assert!(list.is_excluded(Path::new("/home/user/project/node_modules/foo.js")));
+        assert!(list.is_excluded(Path::new("/home/user/project/target/debug/out")));
+        assert!(list.is_excluded(Path::new("/home/user/.cache/thumbnail.png")));
+    }
+
+    #[test]
+    fn defaults_do_not_exclude_ordinary_files() {
+        let list = MatchList::defaults();
+        assert!(!list.is_excluded(Path::new("/home/user/Documents/report.pdf")));
+    }
+
+    #[test]
+    fn later_include_overrides_earlier_exclude() {
+        let mut list = MatchList::new();
+        list.push("**/*", MatchType::Exclude).unwrap();
+        list.push("**/*.pdf", MatchType::Include).unwrap();
+
+        assert!(list.is_excluded(Path::new("/docs/readme.txt")));
+        assert!(!list.is_excluded(Path::new("/docs/report.pdf")));
+    }
+
+    #[test]
+    fn parse_ignore_file_handles_comments_blank_lines_and_negation() {
+        let contents = "\n# comment\n**/snapshots/**\n!keep.snapshot\n!**/snapshots/keep.png\n";
+        let list = MatchList::parse_ignore_file(contents).unwrap();
+
+        assert!(list.is_excluded(Path::new("/proj/snapshots/a.png")));
+        assert!(!list.is_excluded(Path::new("/proj/keep.snapshot")));
+        assert!(!list.is_excluded(Path::new("/proj/snapshots/keep.png")));
+    }
+
+    #[test]
+    fn extended_with_ignore_file_is_a_no_op_when_file_is_absent() {
+        let base = MatchList::defaults();
+        let extended = base.extended_with_ignore_file(Path::new("/nonexistent/root/for/test"));
+        assert!(extended.is_excluded(Path::new("/a/target/b")));
+    }
+}
@@ -0,0 +1,173 @@
+// Author: Enkae (enkae.dev@pm.me)
+//! Media metadata extraction for `IMAGE`/`AUDIO`/`VIDEO` artifacts:
+//! dimensions, duration, codec, bitrate, sample rate, and EXIF capture date,
+//! so the backend has more to index media by than a bare path. The actual
+//! ffmpeg/image decoding lives behind the `media-probe` cargo feature so a
+//! non-media deployment doesn't pull ffmpeg in at all; with the feature off,
+//! `probe_media` is a no-op that reports no extra metadata.
+
+use serde_json::{Map, Value};
+use std::path::Path;
+
+/// Media metadata gathered for one file. Every field is optional: not every
+/// prober extracts every property, and the prober may be compiled out
+/// entirely.
+#[derive(Debug, Clone, Default)]
+pub struct MediaMetadata {
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub duration_seconds: Option<f64>,
+    pub codec: Option<String>,
+    pub bitrate_bps: Option<u64>,
+    pub sample_rate_hz: Option<u32>,
+    pub capture_date: Option<String>,
+    /// A small base64 data URL, image artifacts only, so the backend can do
+    /// multimodal embedding without re-reading the file.
+    pub thumbnail_data_url: Option<String>,
+}
+
+impl MediaMetadata {
+    /// Merges every populated field into `metadata` under its own key.
+    pub fn merge_into(&self, metadata: &mut Map<String, Value>) {
+        if let Some(width) = self.width {
+            metadata.insert("width".to_string(), Value::from(width));
+        }
+        if let Some(height) = self.height {
+            metadata.insert("height".to_string(), Value::from(height));
+        }
+        if let Some(duration) = self.duration_seconds {
+            metadata.insert("duration_seconds".to_string(), Value::from(duration));
+        }
+        if let Some(codec) = &self.codec {
+            metadata.insert("codec".to_string(), Value::from(codec.clone()));
+        }
+        if let Some(bitrate) = self.bitrate_bps {
+            metadata.insert("bitrate_bps".to_string(), Value::from(bitrate));
+        }
+        if let Some(sample_rate) = self.sample_rate_hz {
+            metadata.insert("sample_rate_hz".to_string(), Value::from(sample_rate));
+        }
+        if let Some(capture_date) = &self.capture_date {
+            metadata.insert("capture_date".to_string(), Value::from(capture_date.clone()));
+        }
+        if let Some(thumbnail) = &self.thumbnail_data_url {
+            metadata.insert(
+                "thumbnail_data_url".to_string(),
+                Value::from(thumbnail.clone()),
+            );
+        }
+    }
+}
+
+/// Probes `path` for metadata appropriate to `artifact_type` (`"IMAGE"`,
+/// `"AUDIO"`, or `"VIDEO"`). Returns an empty `MediaMetadata` for any other
+/// type, on a probe failure, or when the `media-probe` feature is disabled.
+pub fn probe_media(path: &Path, artifact_type: &str) -> MediaMetadata {
+    #[cfg(feature = "media-probe")]
+    {
+        match artifact_type {
+            "IMAGE" => probe_image(path),
+            "AUDIO" | "VIDEO" => probe_audio_video(path),
+            _ => MediaMetadata::default(),
+        }
+    }
+
+    #[cfg(not(feature = "media-probe"))]
+    {
+        let _ = (path, artifact_type);
+        MediaMetadata::default()
+    }
+}
+
+#[cfg(feature = "media-probe")]
+fn probe_image(path: &Path) -> MediaMetadata {
+    let mut metadata = MediaMetadata::default();
+
+    match image::open(path) {
+        Ok(img) => {
+            metadata.width = Some(img.width());
+            metadata.height = Some(img.height());
+            metadata.thumbnail_data_url = thumbnail_data_url(&img);
+        }
+        Err(e) => {
+            eprintln!("[MEDIA_PROBE] Failed to decode image {}: {}", path.display(), e);
+        }
+    }
+
+    metadata.capture_date = read_exif_capture_date(path);
+    metadata
+}
+
+#[cfg(feature = "media-probe")]
+fn thumbnail_data_url(img: &image::DynamicImage) -> Option<String> {
+    use base64::Engine;
+
+    let mut bytes: Vec<u8> = Vec::new();
+    img.thumbnail(128, 128)
+        .write_to(&mut std::io::Cursor::new(&mut bytes), image::ImageFormat::Png)
+        .ok()?;
+
+    let encoded = base64::engine::general_purpose::STANDARD.encode(&bytes);
+    Some(format!("data:image/png;base64,{}", encoded))
+}
+
+#[cfg(feature = "media-probe")]
+fn read_exif_capture_date(path: &Path) -> Option<String> {
+    let file = std::fs::File::open(path).ok()?;
+    let mut reader = std::io::BufReader::new(file);
+    let exif = exif::Reader::new().read_from_container(&mut reader).ok()?;
+    let field = exif.get_field(exif::Tag::DateTimeOriginal, exif::In::PRIMARY)?;
+    Some(field.display_value().to_string())
+}
+
+#[cfg(feature = "media-probe")]
+fn probe_audio_video(path: &Path) -> MediaMetadata {
+    let mut metadata = MediaMetadata::default();
+
+    let ctx = match ffmpeg_next::format::input(&path) {
+        Ok(ctx) => ctx,
+        Err(e) => {
+            eprintln!("[MEDIA_PROBE] Failed to open {}: {}", path.display(), e);
+            return metadata;
+        }
+    };
+
+    metadata.duration_seconds =
+        Some(ctx.duration() as f64 / f64::from(ffmpeg_next::ffi::AV_TIME_BASE));
+    metadata.bitrate_bps = Some(ctx.bit_rate() as u64);
+
+    if let Some(video) = ctx.streams().best(ffmpeg_next::media::Type::Video) {
+        fill_video_metadata(&mut metadata, &video);
+    } else if let Some(audio) = ctx.streams().best(ffmpeg_next::media::Type::Audio) {
+        fill_audio_metadata(&mut metadata, &audio);
+    }
+
+    metadata
+}
+
+#[cfg(feature = "media-probe")]
+fn fill_video_metadata(metadata: &mut MediaMetadata, stream: &ffmpeg_next::format::stream::Stream) {
+    let Ok(codec_ctx) = ffmpeg_next::codec::context::Context::from_parameters(stream.parameters())
+    else {
+        return;
+    };
+    metadata.codec = Some(codec_ctx.id().name().to_string());
+
+    if let Ok(decoder) = codec_ctx.decoder().video() {
+        metadata.width = Some(decoder.width());
+        metadata.height = Some(decoder.height());
+    }
+}
+
+#[cfg(feature = "media-probe")]
+fn fill_audio_metadata(metadata: &mut MediaMetadata, stream: &ffmpeg_next::format::stream::Stream) {
+    let Ok(codec_ctx) = ffmpeg_next::codec::context::Context::from_parameters(stream.parameters())
+    else {
+        return;
+    };
+    metadata.codec = Some(codec_ctx.id().name().to_string());
+
+    if let Ok(decoder) = codec_ctx.decoder().audio() {
+        metadata.sample_rate_hz = Some(decoder.rate());
+    }
+}
@@ -0,0 +1,169 @@
+// Author: Enkae (enkae.dev@pm.me)
+//! A small state machine that tracks which modifier keys are currently
+//! held, so a macro step list can hold a modifier across several following
+//! taps (e.g. hold Ctrl, tap K then B) instead of the old single-shot
+//! press-all-then-release-all combo handling, which couldn't express that
+//! and had no way to recover if a release failed mid-sequence.
+
+use crate::effector::{to_enigo_key, InputSink};
+use crate::keybind::Key;
+use anyhow::Result;
+use enigo::Direction;
+use std::collections::HashSet;
+
+/// Is `key` a modifier (held across taps) or a regular key (pressed once)?
+/// `pub(crate)` so callers building their own op sequence (e.g.
+/// `Effector::execute_press_key`'s combo branch) can decide whether a
+/// chord's trailing key should stay held or just be tapped.
+pub(crate) fn is_modifier(key: &Key) -> bool {
+    matches!(key, Key::Control | Key::Shift | Key::Alt | Key::Meta)
+}
+
+/// Tracks the set of currently-pressed modifiers and exposes `press`,
+/// `release`, and `tap` primitives over an `InputSink`. Pressing or
+/// releasing a modifier only emits a sink call when the held set actually
+/// changes; `tap` and non-modifier keys always emit. A guard: any modifier still
+/// held when this drops (normal completion or an early `?` return) is
+/// released, so a failed step never leaves a stuck modifier.
+pub struct ModifierState<'a, S: InputSink> {
+    sink: &'a mut S,
+    held: HashSet<Key>,
+}
+
+impl<'a, S: InputSink> ModifierState<'a, S> {
+    pub fn new(sink: &'a mut S) -> Self {
+        Self { sink, held: HashSet::new() }
+    }
+
+    /// Presses `key`. Modifiers are added to the held set and are a no-op if
+    /// already held; non-modifiers are pressed every time (callers normally
+    /// want `tap` for those instead).
+    pub fn press(&mut self, key: Key) -> Result<()> {
+        if is_modifier(&key) && self.held.contains(&key) {
+            return Ok(());
+        }
+        self.sink.key(to_enigo_key(&key), Direction::Press)?;
+        if is_modifier(&key) {
+            self.held.insert(key);
+        }
+        Ok(())
+    }
+
+    /// Releases `key`. A no-op if it's a modifier that isn't currently held.
+    pub fn release(&mut self, key: Key) -> Result<()> {
+        if is_modifier(&key) && !self.held.contains(&key) {
+            return Ok(());
+        }
+        self.sink.key(to_enigo_key(&key), Direction::Release)?;
+        self.held.remove(&key);
+        Ok(())
+    }
+
+    /// Presses and immediately releases `key`, leaving any held modifiers
+    /// untouched.
+    pub fn tap(&mut self, key: Key) -> Result<()> {
+        self.sink.key(to_enigo_key(&key), Direction::Click)
+    }
+
+    /// Releases every currently-held modifier.
+    pub fn release_all(&mut self) {
+        for key in std::mem::take(&mut self.held) {
+            // Best-effort: a failure here shouldn't mask the original error
+            // that may have triggered this cleanup.
+            let _ = self.sink.key(to_enigo_key(&key), Direction::Release);
+        }
+    }
+}
+
+impl<'a, S: InputSink> Drop for ModifierState<'a, S> {
+    fn drop(&mut self) {
+        self.release_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use enigo::{Button, Key as EnigoKey};
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum RecordedEvent {
+        Key(EnigoKey, Direction),
+    }
+
+    #[derive(Default)]
+    struct RecordingSink {
+        events: Vec<RecordedEvent>,
+    }
+
+    impl InputSink for RecordingSink {
+        fn type_text(&mut self, _text: &str) -> Result<()> {
+            Ok(())
+        }
+        fn move_mouse(&mut self, _x: i32, _y: i32) -> Result<()> {
+            Ok(())
+        }
+        fn click_button(&mut self, _button: Button) -> Result<()> {
+            Ok(())
+        }
+        fn key(&mut self, key: EnigoKey, direction: Direction) -> Result<()> {
+            self.events.push(RecordedEvent::Key(key, direction));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn holds_modifier_across_multiple_taps() {
+        let mut sink = RecordingSink::default();
+        {
+            let mut state = ModifierState::new(&mut sink);
+            state.press(Key::Control).unwrap();
+            state.tap(Key::Char('k')).unwrap();
+            state.tap(Key::Char('b')).unwrap();
+            state.release(Key::Control).unwrap();
+        }
+
+        assert_eq!(
+            sink.events,
+            vec![
+                RecordedEvent::Key(EnigoKey::Control, Direction::Press),
+                RecordedEvent::Key(EnigoKey::Unicode('k'), Direction::Click),
+                RecordedEvent::Key(EnigoKey::Unicode('b'), Direction::Click),
+                RecordedEvent::Key(EnigoKey::Control, Direction::Release),
+            ]
+        );
+    }
+
+    #[test]
+    fn pressing_an_already_held_modifier_is_a_no_op() {
+        let mut sink = RecordingSink::default();
+        {
+            let mut state = ModifierState::new(&mut sink);
+            state.press(Key::Control).unwrap();
+            state.press(Key::Control).unwrap();
+        }
+
+        assert_eq!(
+            sink.events,
+            vec![RecordedEvent::Key(EnigoKey::Control, Direction::Press)]
+        );
+    }
+
+    #[test]
+    fn drop_releases_any_modifier_left_held() {
+        let mut sink = RecordingSink::default();
+        {
+            let mut state = ModifierState::new(&mut sink);
+            state.press(Key::Shift).unwrap();
+            // No explicit release: the guard must clean up on drop.
+        }
+
+        assert_eq!(
+            sink.events,
+            vec![
+                RecordedEvent::Key(EnigoKey::Shift, Direction::Press),
+                RecordedEvent::Key(EnigoKey::Shift, Direction::Release),
+            ]
+        );
+    }
+}
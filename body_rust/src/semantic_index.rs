@@ -0,0 +1,281 @@
+// Author: Enkae (enkae.dev@pm.me)
+//! Semantic index over the Librarian's text-extractable artifacts: each
+//! chunk of an indexed file gets an embedding vector, held in a small
+//! in-memory vector store, so the hybrid daemon's `SEARCH` command can
+//! answer "where did I put the file about X" instead of only exact-path
+//! lookups. Embeddings come from a pluggable `Embedder` — a remote HTTP
+//! endpoint by default, or the `local-embeddings` cargo feature's on-device
+//! model when a network round-trip per chunk isn't acceptable. `Librarian`
+//! always constructs a `RemoteEmbedder`; nothing in this crate selects
+//! `LocalEmbedder`.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// `chunk_text`'s window is sized in characters; this approximates the
+/// ~512-token windows embedding models are tuned for, at roughly four
+/// characters per token.
+const EMBED_WINDOW_CHARS: usize = 2000;
+const EMBED_OVERLAP_CHARS: usize = 400;
+
+/// Produces an embedding vector for a chunk of text (or a search query —
+/// the same embedding space is used for both, so they're comparable by
+/// cosine similarity).
+pub trait Embedder: Send + Sync {
+    fn embed(&self, text: &str) -> Result<Vec<f32>>;
+}
+
+/// Calls a backend embeddings endpoint over HTTP. The default `Embedder`:
+/// no extra binary size or model file, at the cost of a round-trip per
+/// chunk.
+pub struct RemoteEmbedder {
+    endpoint: String,
+}
+
+impl RemoteEmbedder {
+    /// `endpoint` is the full embeddings URL, e.g.
+    /// `format!("{}/api/embeddings", api_url)` — same convention as
+    /// `Librarian::new`'s `api_url` for artifacts.
+    pub fn new(endpoint: String) -> Self {
+        Self { endpoint }
+    }
+}
+
+impl Embedder for RemoteEmbedder {
+    fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        let response = reqwest::blocking::Client::new()
+            .post(&self.endpoint)
+            .json(&serde_json::json!({ "text": text }))
+            .send()
+            .context("Failed to reach embeddings endpoint")?;
+
+        let body: serde_json::Value = response.json().context("Malformed embeddings response")?;
+        body.get("embedding")
+            .and_then(|v| v.as_array())
+            .map(|values| values.iter().filter_map(|v| v.as_f64()).map(|v| v as f32).collect())
+            .ok_or_else(|| anyhow::anyhow!("Embeddings response missing 'embedding' array"))
+    }
+}
+
+/// On-device embedding via a small local BERT-family model, for deployments
+/// that can't afford a network round-trip per chunk. Behind a cargo feature
+/// for the same reason `media_probe`'s ffmpeg path is: a deployment that
+/// doesn't use semantic search shouldn't pull in a model runtime and weights
+/// file at all.
+#[cfg(feature = "local-embeddings")]
+pub struct LocalEmbedder {
+    model: candle_transformers::models::bert::BertModel,
+    tokenizer: tokenizers::Tokenizer,
+    device: candle_core::Device,
+}
+
+#[cfg(feature = "local-embeddings")]
+impl LocalEmbedder {
+    /// Loads a model directory in the usual Hugging Face layout:
+    /// `config.json`, `tokenizer.json`, `model.safetensors`.
+    pub fn load(model_dir: &Path) -> Result<Self> {
+        let device = candle_core::Device::Cpu;
+
+        let tokenizer = tokenizers::Tokenizer::from_file(model_dir.join("tokenizer.json"))
+            .map_err(|e| anyhow::anyhow!("Failed to load tokenizer: {}", e))?;
+
+        let config_json = std::fs::read_to_string(model_dir.join("config.json"))
+            .context("Failed to read model config.json")?;
+        let config: candle_transformers::models::bert::Config =
+            serde_json::from_str(&config_json).context("Failed to parse model config.json")?;
+
+        let weights =
+            unsafe { candle_core::safetensors::MmapedSafetensors::new(model_dir.join("model.safetensors"))? };
+        let vb = candle_nn::VarBuilder::from_backend(Box::new(weights), candle_core::DType::F32, device.clone());
+        let model = candle_transformers::models::bert::BertModel::load(vb, &config)
+            .context("Failed to construct BERT model")?;
+
+        Ok(Self { model, tokenizer, device })
+    }
+}
+
+#[cfg(feature = "local-embeddings")]
+impl Embedder for LocalEmbedder {
+    fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        let encoding = self
+            .tokenizer
+            .encode(text, true)
+            .map_err(|e| anyhow::anyhow!("Tokenization failed: {}", e))?;
+
+        let ids = candle_core::Tensor::new(encoding.get_ids(), &self.device)?.unsqueeze(0)?;
+        let token_type_ids = ids.zeros_like()?;
+        let token_embeddings = self.model.forward(&ids, &token_type_ids)?;
+
+        // Mean-pool token embeddings into a single sentence vector.
+        let (_, seq_len, _) = token_embeddings.dims3()?;
+        let pooled = (token_embeddings.sum(1)? / seq_len as f64)?;
+        pooled.squeeze(0)?.to_vec1::<f32>().context("Failed to read pooled embedding")
+    }
+}
+
+/// One chunk's embedding, plus enough to re-locate and preview it in a
+/// search result.
+#[derive(Debug, Clone)]
+pub struct VectorEntry {
+    pub path: PathBuf,
+    pub chunk_index: usize,
+    pub char_start: usize,
+    pub char_end: usize,
+    pub snippet: String,
+    pub vector: Vec<f32>,
+}
+
+/// An in-memory store of every indexed file's chunk vectors. Cheap to
+/// clone (shares the same `Mutex`-guarded list), so it can be handed to the
+/// index job's worker pool, the file watcher's event handler, and the
+/// hybrid daemon's `SEARCH` handler alike.
+#[derive(Clone, Default)]
+pub struct VectorStore {
+    entries: Arc<Mutex<Vec<VectorEntry>>>,
+}
+
+impl VectorStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces every entry for `path` with `entries`, so a file's vectors
+    /// never go stale after a re-index (the file watcher calls this when it
+    /// re-indexes a created or changed document, not only the initial walk).
+    pub fn upsert_file(&self, path: &Path, entries: Vec<VectorEntry>) {
+        let mut store = self.entries.lock().unwrap();
+        store.retain(|e| e.path != path);
+        store.extend(entries);
+    }
+
+    /// Drops every vector for `path`, e.g. when the file watcher sees it
+    /// removed.
+    pub fn remove_file(&self, path: &Path) {
+        self.entries.lock().unwrap().retain(|e| e.path != path);
+    }
+
+    /// Returns up to `top_k` entries whose vector is most cosine-similar to
+    /// `query_vector`, highest similarity first.
+    pub fn search(&self, query_vector: &[f32], top_k: usize) -> Vec<(f32, VectorEntry)> {
+        let store = self.entries.lock().unwrap();
+        let mut scored: Vec<(f32, VectorEntry)> = store
+            .iter()
+            .map(|entry| (cosine_similarity(query_vector, &entry.vector), entry.clone()))
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        scored.truncate(top_k);
+        scored
+    }
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+/// Chunks and embeds `text` (the extracted content of one text-extractable
+/// file), returning one `VectorEntry` per chunk. A chunk whose embedding
+/// call fails is logged and skipped rather than aborting the whole file.
+pub fn embed_file(embedder: &dyn Embedder, path: &Path, text: &str) -> Vec<VectorEntry> {
+    crate::document_loader::chunk_text(text, EMBED_WINDOW_CHARS, EMBED_OVERLAP_CHARS)
+        .into_iter()
+        .filter_map(|chunk| match embedder.embed(&chunk.text) {
+            Ok(vector) => Some(VectorEntry {
+                path: path.to_path_buf(),
+                chunk_index: chunk.chunk_index,
+                char_start: chunk.char_start,
+                char_end: chunk.char_end,
+                snippet: chunk.text,
+                vector,
+            }),
+            Err(e) => {
+                eprintln!(
+                    "[SEMANTIC_INDEX] Failed to embed chunk {} of {}: {}",
+                    chunk.chunk_index,
+                    path.display(),
+                    e
+                );
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &str, vector: Vec<f32>) -> VectorEntry {
+        VectorEntry {
+            path: PathBuf::from(path),
+            chunk_index: 0,
+            char_start: 0,
+            char_end: 0,
+            snippet: String::new(),
+            vector,
+        }
+    }
+
+    #[test]
+    fn cosine_similarity_identical_vectors_is_one() {
+        assert!((cosine_similarity(&[1.0, 2.0, 3.0], &[1.0, 2.0, 3.0]) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn cosine_similarity_orthogonal_vectors_is_zero() {
+        assert!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]).abs() < 1e-6);
+    }
+
+    #[test]
+    fn cosine_similarity_zero_vector_is_zero_not_nan() {
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 2.0]), 0.0);
+    }
+
+    #[test]
+    fn search_returns_top_k_by_similarity_descending() {
+        let store = VectorStore::new();
+        store.upsert_file(
+            Path::new("a"),
+            vec![
+                entry("a", vec![1.0, 0.0]),
+                entry("a", vec![0.0, 1.0]),
+                entry("a", vec![0.9, 0.1]),
+            ],
+        );
+
+        let results = store.search(&[1.0, 0.0], 2);
+        assert_eq!(results.len(), 2);
+        assert!(results[0].0 >= results[1].0);
+    }
+
+    #[test]
+    fn upsert_file_replaces_previous_entries_for_that_path() {
+        let store = VectorStore::new();
+        store.upsert_file(Path::new("a"), vec![entry("a", vec![1.0, 0.0])]);
+        store.upsert_file(Path::new("a"), vec![entry("a", vec![0.0, 1.0])]);
+
+        let results = store.search(&[0.0, 1.0], 10);
+        assert_eq!(results.len(), 1);
+    }
+
+    #[test]
+    fn remove_file_drops_its_entries() {
+        let store = VectorStore::new();
+        store.upsert_file(Path::new("a"), vec![entry("a", vec![1.0, 0.0])]);
+        store.remove_file(Path::new("a"));
+
+        assert!(store.search(&[1.0, 0.0], 10).is_empty());
+    }
+}